@@ -0,0 +1,146 @@
+//! `bindgen` crate integration
+
+use std::path::Path;
+
+use crate::{push_unique, CargoInstructions, ConanError, ConanNode};
+
+impl CargoInstructions {
+    /// Configures the `bindgen` builder with the include paths (`-I`)
+    /// and preprocessor definitions (`-D`) of all dependencies.
+    ///
+    /// Requires the `bindgen` crate feature.
+    #[must_use]
+    pub fn configure_bindgen(&self, builder: bindgen::Builder) -> bindgen::Builder {
+        builder
+            .clang_args(self.includes.iter().map(|path| include_arg(path)))
+            .clang_args(self.defines.iter().map(|define| format!("-D{define}")))
+    }
+
+    /// Configures the `bindgen` builder to generate bindings only for
+    /// the headers of the host context package named `package`.
+    ///
+    /// Adds the include paths (`-I`) and preprocessor definitions (`-D`)
+    /// of the package and its dependencies, and the `allowlist_file()`
+    /// patterns matching the headers in the package include directories.
+    ///
+    /// Requires the `bindgen` crate feature.
+    ///
+    /// ```no_run
+    /// use conan2::ConanInstall;
+    ///
+    /// let metadata = ConanInstall::new().run().parse();
+    ///
+    /// metadata
+    ///     .configure_bindgen_package(bindgen::builder(), "openssl")
+    ///     .expect("OpenSSL is not a Conan dependency")
+    ///     .header("src/openssl.h")
+    ///     .generate()
+    ///     .expect("failed to generate OpenSSL bindings")
+    ///     .write_to_file("src/openssl_sys.rs")
+    ///     .unwrap();
+    ///
+    /// metadata.emit();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConanError::PackageNotFound`] if the package
+    /// is not found in the Conan dependency graph.
+    pub fn configure_bindgen_package(
+        &self,
+        mut builder: bindgen::Builder,
+        package: &str,
+    ) -> Result<bindgen::Builder, ConanError> {
+        let graph = self.graph();
+        let node = graph
+            .package(package)
+            .ok_or_else(|| ConanError::PackageNotFound(package.to_owned()))?;
+
+        for component in node.cpp_info() {
+            for include in component.includedirs() {
+                let pattern = format!("{}/.*", regex_escape(&include.to_string_lossy()));
+                builder = builder.allowlist_file(pattern);
+            }
+        }
+
+        // The package headers may include the dependency headers.
+        let dependencies = node
+            .dependencies()
+            .iter()
+            .filter(|dependency| dependency.headers())
+            .filter_map(|dependency| graph.node(dependency.id()));
+
+        let mut includes = Vec::new();
+        let mut defines = Vec::new();
+
+        for component in std::iter::once(node)
+            .chain(dependencies)
+            .flat_map(ConanNode::cpp_info)
+        {
+            for include in component.includedirs() {
+                push_unique(&mut includes, &include_arg(include));
+            }
+
+            for define in component.defines() {
+                push_unique(&mut defines, &format!("-D{define}"));
+            }
+        }
+
+        Ok(builder.clang_args(includes).clang_args(defines))
+    }
+}
+
+/// Formats the `-I{path}` clang argument.
+fn include_arg(path: &Path) -> String {
+    format!("-I{}", path.display())
+}
+
+/// Escapes the regular expression special characters.
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$#&-~".contains(c) {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{regex_escape, CargoInstructions, ConanError};
+
+    #[test]
+    fn package_bindgen_args() {
+        let json = std::fs::read("tests/data/conan_graph.json").unwrap();
+        let cargo = CargoInstructions::from_graph_json(&json).unwrap();
+
+        let flags = cargo
+            .configure_bindgen_package(bindgen::builder(), "libxml2")
+            .unwrap()
+            .command_line_flags();
+
+        assert!(flags.contains(&"/conan/p/libxml2/p/include/libxml2/.*".to_owned()));
+        assert!(flags.contains(&"-I/conan/p/libxml2/p/include/libxml2".to_owned()));
+        assert!(flags.contains(&"-I/conan/p/zlib/p/include".to_owned()));
+        assert!(flags.contains(&"-DLIBXML_STATIC".to_owned()));
+        assert!(!flags.contains(&"-I/conan/p/openssl/p/include".to_owned()));
+
+        let err = cargo
+            .configure_bindgen_package(bindgen::builder(), "missing")
+            .unwrap_err();
+        assert!(matches!(err, ConanError::PackageNotFound(package) if package == "missing"));
+    }
+
+    #[test]
+    fn escape_regex() {
+        assert_eq!(
+            regex_escape("C:\\conan\\p\\a.b+c"),
+            "C:\\\\conan\\\\p\\\\a\\.b\\+c"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! `conan install` output cache keyed by the install inputs fingerprint
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use crate::ConanError;
+
+/// Cached `conan install` JSON output file name
+const OUTPUT_FILE: &str = "conan_install.json";
+
+/// Cached `conan install` stderr output file name
+const STDERR_FILE: &str = "conan_install.stderr";
+
+/// Cached `conan install` inputs fingerprint file name
+const FINGERPRINT_FILE: &str = "conan_install.fingerprint";
+
+/// FNV-1a 64-bit hash offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit hash prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// `conan install` inputs fingerprint builder
+///
+/// The FNV-1a hash is used to keep the fingerprints stable
+/// across the Rust toolchain versions.
+pub(crate) struct Fingerprint(u64);
+
+/// `conan install` JSON and stderr output cache stored in the output folder
+pub(crate) struct InstallCache {
+    /// Cached JSON output file path
+    output_path: PathBuf,
+    /// Cached stderr output file path
+    stderr_path: PathBuf,
+    /// Cached inputs fingerprint file path
+    fingerprint_path: PathBuf,
+}
+
+impl Fingerprint {
+    /// Creates a new empty fingerprint.
+    pub fn new() -> Fingerprint {
+        Fingerprint(FNV_OFFSET_BASIS)
+    }
+
+    /// Adds a command line argument or any other string value.
+    pub fn value(&mut self, value: &OsStr) -> &mut Fingerprint {
+        self.write(value.as_encoded_bytes());
+        self
+    }
+
+    /// Adds the environment variable name and value.
+    pub fn env(&mut self, name: &str) -> &mut Fingerprint {
+        self.write(name.as_bytes());
+        self.write_option(
+            std::env::var_os(name)
+                .as_deref()
+                .map(OsStr::as_encoded_bytes),
+        );
+        self
+    }
+
+    /// Adds the file path and contents.
+    pub fn file(&mut self, path: &Path) -> &mut Fingerprint {
+        self.write(path.as_os_str().as_encoded_bytes());
+        self.write_option(std::fs::read(path).ok().as_deref());
+        self
+    }
+
+    /// Gets the fingerprint value as a hex string.
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+
+    /// Hashes the length-prefixed bytes, so that the adjacent values
+    /// cannot be confused with each other.
+    fn write(&mut self, bytes: &[u8]) {
+        let len = u64::try_from(bytes.len()).unwrap_or(u64::MAX).to_le_bytes();
+
+        for byte in len.iter().chain(bytes) {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Hashes the optional bytes distinguishing the missing value from the empty one.
+    fn write_option(&mut self, bytes: Option<&[u8]>) {
+        match bytes {
+            Some(bytes) => {
+                self.write(&[1]);
+                self.write(bytes);
+            }
+            None => self.write(&[0]),
+        }
+    }
+}
+
+impl InstallCache {
+    /// Creates the `conan install` output cache in the output folder.
+    pub fn new(output_folder: &Path) -> InstallCache {
+        InstallCache {
+            output_path: output_folder.join(OUTPUT_FILE),
+            stderr_path: output_folder.join(STDERR_FILE),
+            fingerprint_path: output_folder.join(FINGERPRINT_FILE),
+        }
+    }
+
+    /// Loads the cached JSON and stderr outputs if the fingerprint matches.
+    pub fn load(&self, fingerprint: &str) -> Option<(Vec<u8>, Vec<u8>)> {
+        let saved = std::fs::read_to_string(&self.fingerprint_path).ok()?;
+
+        if saved != fingerprint {
+            return None;
+        }
+
+        let stdout = std::fs::read(&self.output_path).ok()?;
+        let stderr = std::fs::read(&self.stderr_path).ok()?;
+
+        Some((stdout, stderr))
+    }
+
+    /// Stores the JSON and stderr outputs with their inputs fingerprint.
+    pub fn store(&self, fingerprint: &str, stdout: &[u8], stderr: &[u8]) -> Result<(), ConanError> {
+        // Invalidate the previous fingerprint first in case writing the output fails.
+        let _ = std::fs::remove_file(&self.fingerprint_path);
+
+        Self::write(&self.output_path, stdout)?;
+        Self::write(&self.stderr_path, stderr)?;
+        Self::write(&self.fingerprint_path, fingerprint.as_bytes())
+    }
+
+    /// Writes the cache file.
+    fn write(path: &Path, contents: &[u8]) -> Result<(), ConanError> {
+        std::fs::write(path, contents).map_err(|source| ConanError::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use super::Fingerprint;
+
+    #[test]
+    fn stable_fingerprint() {
+        assert_eq!(Fingerprint::new().finish(), "cbf29ce484222325");
+
+        let fingerprint = Fingerprint::new()
+            .value(OsStr::new("install"))
+            .value(OsStr::new("--build=missing"))
+            .finish();
+        assert_eq!(fingerprint, "6631d5288887d481");
+
+        // The values are length-prefixed.
+        let joined = Fingerprint::new()
+            .value(OsStr::new("install--build=missing"))
+            .finish();
+        assert_ne!(joined, fingerprint);
+    }
+}
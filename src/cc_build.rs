@@ -0,0 +1,60 @@
+//! `cc` crate integration
+
+use crate::CargoInstructions;
+
+impl CargoInstructions {
+    /// Configures the `cc` crate C compiler build with the include paths,
+    /// preprocessor definitions and C compiler flags of all dependencies.
+    ///
+    /// Requires the `cc` crate feature.
+    ///
+    /// ```no_run
+    /// use conan2::ConanInstall;
+    ///
+    /// let metadata = ConanInstall::new().run().parse();
+    ///
+    /// metadata
+    ///     .configure_cc(&mut cc::Build::new())
+    ///     .file("src/glue.c")
+    ///     .compile("glue");
+    ///
+    /// metadata.emit();
+    /// ```
+    pub fn configure_cc<'a>(&self, build: &'a mut cc::Build) -> &'a mut cc::Build {
+        self.configure_common(build);
+
+        for flag in &self.cflags {
+            build.flag(flag);
+        }
+
+        build
+    }
+
+    /// Configures the `cc` crate C++ compiler build with the include paths,
+    /// preprocessor definitions and C++ compiler flags of all dependencies.
+    ///
+    /// Also enables C++ compilation with `cc::Build::cpp(true)`.
+    ///
+    /// Requires the `cc` crate feature.
+    pub fn configure_cxx<'a>(&self, build: &'a mut cc::Build) -> &'a mut cc::Build {
+        self.configure_common(build.cpp(true));
+
+        for flag in &self.cxxflags {
+            build.flag(flag);
+        }
+
+        build
+    }
+
+    /// Adds the include paths and preprocessor definitions of all dependencies.
+    fn configure_common(&self, build: &mut cc::Build) {
+        build.includes(&self.includes);
+
+        for define in &self.defines {
+            match define.split_once('=') {
+                Some((name, value)) => build.define(name, value),
+                None => build.define(define, None),
+            };
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! `cmake` crate integration
+
+use crate::generated::CMAKE_TOOLCHAIN_FILE;
+use crate::{CargoInstructions, ConanError};
+
+impl CargoInstructions {
+    /// Configures the `cmake` crate build to find the Conan dependencies.
+    ///
+    /// Sets `CMAKE_TOOLCHAIN_FILE` to the `conan_toolchain.cmake` file and
+    /// `CMAKE_PREFIX_PATH` to the generators folder of the consumer,
+    /// and matches the CMake build type to the Conan `build_type` setting.
+    ///
+    /// Use [`ConanInstall::generator()`](crate::ConanInstall::generator)
+    /// to request `CMakeToolchain` and `CMakeDeps` generators
+    /// if the recipe does not list them.
+    ///
+    /// Requires the `cmake` crate feature.
+    ///
+    /// ```no_run
+    /// use conan2::ConanInstall;
+    ///
+    /// let metadata = ConanInstall::new()
+    ///     .generator("CMakeToolchain")
+    ///     .generator("CMakeDeps")
+    ///     .run()
+    ///     .parse();
+    ///
+    /// let dst = metadata
+    ///     .configure_cmake(&mut cmake::Config::new("cpp"))
+    ///     .expect("Conan generators folder is not known")
+    ///     .build();
+    ///
+    /// println!("cargo:rustc-link-search=native={}/lib", dst.display());
+    /// metadata.emit();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConanError::UnexpectedSchema`] if the Conan dependency graph
+    /// does not report the generators folder of the consumer.
+    pub fn configure_cmake<'a>(
+        &self,
+        config: &'a mut cmake::Config,
+    ) -> Result<&'a mut cmake::Config, ConanError> {
+        let root = self.graph().root();
+
+        let generators_folder =
+            root.and_then(|root| root.generators_folder())
+                .ok_or(ConanError::UnexpectedSchema(
+                    "consumer 'generators_folder' expected",
+                ))?;
+
+        config
+            .define(
+                "CMAKE_TOOLCHAIN_FILE",
+                generators_folder.join(CMAKE_TOOLCHAIN_FILE),
+            )
+            .define("CMAKE_PREFIX_PATH", generators_folder);
+
+        if let Some(build_type) = root.and_then(|root| root.settings().get("build_type")) {
+            config.profile(build_type);
+        }
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,273 @@
+//! Conan package `cpp_info` component model
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::graph::{get_string, get_string_map};
+use crate::ConanPackageType;
+
+/// Conan package C/C++ component information
+///
+/// Matches a single `cpp_info` component of a Conan package as reported
+/// in the JSON-formatted Conan dependency graph.
+/// The packages without components have a single `root` component.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CppComponent {
+    /// Component name
+    name: String,
+    /// Include directory paths
+    includedirs: Vec<PathBuf>,
+    /// Source directory paths
+    srcdirs: Vec<PathBuf>,
+    /// Library directory paths
+    libdirs: Vec<PathBuf>,
+    /// Resource directory paths
+    resdirs: Vec<PathBuf>,
+    /// Binary directory paths
+    bindirs: Vec<PathBuf>,
+    /// Build directory paths
+    builddirs: Vec<PathBuf>,
+    /// Framework directory paths (Apple only)
+    frameworkdirs: Vec<PathBuf>,
+    /// Packaged library names
+    libs: Vec<String>,
+    /// System library names
+    system_libs: Vec<String>,
+    /// Framework names (Apple only)
+    frameworks: Vec<String>,
+    /// Preprocessor definitions
+    defines: Vec<String>,
+    /// C compiler flags
+    cflags: Vec<String>,
+    /// C++ compiler flags
+    cxxflags: Vec<String>,
+    /// Shared library linker flags
+    sharedlinkflags: Vec<String>,
+    /// Executable linker flags
+    exelinkflags: Vec<String>,
+    /// Object file paths
+    objects: Vec<PathBuf>,
+    /// Sysroot path
+    sysroot: Option<PathBuf>,
+    /// Required components: `comp` or `pkg::comp`
+    requires: Vec<String>,
+    /// Generator-specific properties
+    properties: BTreeMap<String, String>,
+    /// Executable name
+    exe: Option<String>,
+    /// Component library type
+    type_: Option<ConanPackageType>,
+    /// Library file path
+    location: Option<PathBuf>,
+    /// Import library file path (Windows only)
+    link_location: Option<PathBuf>,
+    /// Component languages: `C` and/or `C++`
+    languages: Vec<String>,
+}
+
+impl CppComponent {
+    /// Gets the component name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the include directory paths.
+    #[must_use]
+    pub fn includedirs(&self) -> &[PathBuf] {
+        &self.includedirs
+    }
+
+    /// Gets the source directory paths.
+    #[must_use]
+    pub fn srcdirs(&self) -> &[PathBuf] {
+        &self.srcdirs
+    }
+
+    /// Gets the library directory paths.
+    #[must_use]
+    pub fn libdirs(&self) -> &[PathBuf] {
+        &self.libdirs
+    }
+
+    /// Gets the resource directory paths.
+    #[must_use]
+    pub fn resdirs(&self) -> &[PathBuf] {
+        &self.resdirs
+    }
+
+    /// Gets the binary directory paths.
+    #[must_use]
+    pub fn bindirs(&self) -> &[PathBuf] {
+        &self.bindirs
+    }
+
+    /// Gets the build directory paths.
+    #[must_use]
+    pub fn builddirs(&self) -> &[PathBuf] {
+        &self.builddirs
+    }
+
+    /// Gets the framework directory paths (Apple only).
+    #[must_use]
+    pub fn frameworkdirs(&self) -> &[PathBuf] {
+        &self.frameworkdirs
+    }
+
+    /// Gets the packaged library names.
+    #[must_use]
+    pub fn libs(&self) -> &[String] {
+        &self.libs
+    }
+
+    /// Gets the system library names.
+    #[must_use]
+    pub fn system_libs(&self) -> &[String] {
+        &self.system_libs
+    }
+
+    /// Gets the framework names (Apple only).
+    #[must_use]
+    pub fn frameworks(&self) -> &[String] {
+        &self.frameworks
+    }
+
+    /// Gets the preprocessor definitions: `NAME` or `NAME=VALUE`.
+    #[must_use]
+    pub fn defines(&self) -> &[String] {
+        &self.defines
+    }
+
+    /// Gets the C compiler flags.
+    #[must_use]
+    pub fn cflags(&self) -> &[String] {
+        &self.cflags
+    }
+
+    /// Gets the C++ compiler flags.
+    #[must_use]
+    pub fn cxxflags(&self) -> &[String] {
+        &self.cxxflags
+    }
+
+    /// Gets the shared library linker flags.
+    #[must_use]
+    pub fn sharedlinkflags(&self) -> &[String] {
+        &self.sharedlinkflags
+    }
+
+    /// Gets the executable linker flags.
+    #[must_use]
+    pub fn exelinkflags(&self) -> &[String] {
+        &self.exelinkflags
+    }
+
+    /// Gets the object file paths.
+    #[must_use]
+    pub fn objects(&self) -> &[PathBuf] {
+        &self.objects
+    }
+
+    /// Gets the sysroot path.
+    #[must_use]
+    pub fn sysroot(&self) -> Option<&Path> {
+        self.sysroot.as_deref()
+    }
+
+    /// Gets the required components: `comp` for the components
+    /// of the same package and `pkg::comp` for other packages.
+    #[must_use]
+    pub fn requires(&self) -> &[String] {
+        &self.requires
+    }
+
+    /// Gets the generator-specific properties, e.g. `pkg_config_name`.
+    ///
+    /// The non-string property values are rendered as JSON.
+    #[must_use]
+    pub fn properties(&self) -> &BTreeMap<String, String> {
+        &self.properties
+    }
+
+    /// Gets the executable name.
+    #[must_use]
+    pub fn exe(&self) -> Option<&str> {
+        self.exe.as_deref()
+    }
+
+    /// Gets the component library type (the `type` field).
+    #[must_use]
+    pub fn type_(&self) -> Option<ConanPackageType> {
+        self.type_
+    }
+
+    /// Gets the library file path.
+    #[must_use]
+    pub fn location(&self) -> Option<&Path> {
+        self.location.as_deref()
+    }
+
+    /// Gets the import library file path (Windows only).
+    #[must_use]
+    pub fn link_location(&self) -> Option<&Path> {
+        self.link_location.as_deref()
+    }
+
+    /// Gets the component languages: `C` and/or `C++`.
+    #[must_use]
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Converts the JSON `cpp_info` component object into the typed component.
+    pub(crate) fn from_map(name: &str, component: &Map<String, Value>) -> CppComponent {
+        let strings = |key| get_strings(component, key);
+        let paths = |key| strings(key).into_iter().map(PathBuf::from).collect();
+        let path = |key| get_string(component, key).map(PathBuf::from);
+
+        CppComponent {
+            name: name.to_owned(),
+            includedirs: paths("includedirs"),
+            srcdirs: paths("srcdirs"),
+            libdirs: paths("libdirs"),
+            resdirs: paths("resdirs"),
+            bindirs: paths("bindirs"),
+            builddirs: paths("builddirs"),
+            frameworkdirs: paths("frameworkdirs"),
+            libs: strings("libs"),
+            system_libs: strings("system_libs"),
+            frameworks: strings("frameworks"),
+            defines: strings("defines"),
+            cflags: strings("cflags"),
+            cxxflags: strings("cxxflags"),
+            sharedlinkflags: strings("sharedlinkflags"),
+            exelinkflags: strings("exelinkflags"),
+            objects: paths("objects"),
+            sysroot: path("sysroot"),
+            requires: strings("requires"),
+            properties: get_string_map(component, "properties"),
+            exe: get_string(component, "exe"),
+            type_: get_string(component, "type").map(|t| ConanPackageType::from_str(&t)),
+            location: path("location"),
+            link_location: path("link_location"),
+            languages: strings("languages"),
+        }
+    }
+}
+
+/// Gets the JSON object string array field skipping the non-string items.
+fn get_strings(map: &Map<String, Value>, key: &str) -> Vec<String> {
+    let Some(Value::Array(values)) = map.get(key) else {
+        return Vec::new();
+    };
+
+    values
+        .iter()
+        .filter_map(|value| match value {
+            Value::String(value) => Some(value.clone()),
+            _ => None,
+        })
+        .collect()
+}
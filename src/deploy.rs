@@ -0,0 +1,154 @@
+//! Runtime shared library deployment
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::{CargoInstructions, ConanError};
+
+/// Runtime shared library deployment method
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConanDeployMethod {
+    /// Copy the shared library files
+    #[default]
+    Copy,
+    /// Create symbolic links to the shared library files in the Conan cache
+    Symlink,
+}
+
+impl CargoInstructions {
+    /// Deploys the runtime shared libraries of all linked host dependencies
+    /// into the Cargo target profile directory, e.g. `target/debug`.
+    ///
+    /// Makes the binaries linked against the shared Conan packages
+    /// runnable with `cargo run` and `cargo test` without installing
+    /// the shared libraries system-wide.
+    ///
+    /// The shared libraries (`*.so*`, `*.dylib` and `*.dll` files) are
+    /// looked up in the package `bindirs` and `libdirs` directories.
+    ///
+    /// Returns the list of deployed file paths.
+    ///
+    /// NOTE: The Conan `runtime_deploy` deployer can be used instead via
+    ///       [`ConanInstall::deployer()`](crate::ConanInstall::deployer).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shared library file could not be deployed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `OUT_DIR` environment variable is not defined.
+    pub fn deploy_runtime_libs(
+        &self,
+        method: ConanDeployMethod,
+    ) -> Result<Vec<PathBuf>, ConanError> {
+        self.deploy_runtime_libs_to(method, &target_profile_dir())
+    }
+
+    /// Deploys the runtime shared libraries of all linked host dependencies
+    /// into the `folder` directory.
+    ///
+    /// This is a version of [`CargoInstructions::deploy_runtime_libs()`]
+    /// with a custom deployment directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shared library file could not be deployed.
+    pub fn deploy_runtime_libs_to(
+        &self,
+        method: ConanDeployMethod,
+        folder: &Path,
+    ) -> Result<Vec<PathBuf>, ConanError> {
+        let mut deployed = Vec::new();
+
+        for source in self.runtime_libs() {
+            let Some(file_name) = source.file_name() else {
+                continue;
+            };
+
+            let target = folder.join(file_name);
+            deploy_file(method, &source, &target).map_err(|source| ConanError::Io {
+                path: target.clone(),
+                source,
+            })?;
+
+            deployed.push(target);
+        }
+
+        Ok(deployed)
+    }
+
+    /// Finds the shared library files of all linked host dependencies.
+    fn runtime_libs(&self) -> Vec<PathBuf> {
+        let dirs: BTreeSet<&Path> = self
+            .runtime_components()
+            .into_iter()
+            .flat_map(|(_, component)| component.bindirs().iter().chain(component.libdirs()))
+            .map(PathBuf::as_path)
+            .collect();
+
+        let mut libs = Vec::new();
+
+        for dir in dirs {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+
+            let mut dir_libs: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| is_shared_lib(path) && path.is_file())
+                .collect();
+
+            dir_libs.sort();
+            libs.append(&mut dir_libs);
+        }
+
+        libs
+    }
+}
+
+/// Gets the Cargo target profile directory from `OUT_DIR`:
+/// `target/[{triple}/]{profile}/build/{package}-{hash}/out`.
+pub(crate) fn target_profile_dir() -> PathBuf {
+    let out_dir = PathBuf::from(
+        std::env::var_os("OUT_DIR").expect("OUT_DIR environment variable must be set"),
+    );
+
+    out_dir
+        .ancestors()
+        .nth(3)
+        .expect("unexpected OUT_DIR directory layout")
+        .to_owned()
+}
+
+/// Checks if the file name matches the shared library naming patterns:
+/// `libfoo.so`, `libfoo.so.1.2`, `libfoo.dylib` or `foo.dll`.
+fn is_shared_lib(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    name.ends_with(".so")
+        || name.contains(".so.")
+        || name.ends_with(".dylib")
+        || name.to_ascii_lowercase().ends_with(".dll")
+}
+
+/// Copies or symlinks the `source` file to the `target` path.
+fn deploy_file(method: ConanDeployMethod, source: &Path, target: &Path) -> std::io::Result<()> {
+    // Replace the stale files and symlinks left from the previous builds.
+    if target.symlink_metadata().is_ok() {
+        std::fs::remove_file(target)?;
+    }
+
+    match method {
+        ConanDeployMethod::Copy => std::fs::copy(source, target).map(|_| ()),
+        #[cfg(unix)]
+        ConanDeployMethod::Symlink => std::os::unix::fs::symlink(source, target),
+        #[cfg(windows)]
+        ConanDeployMethod::Symlink => std::os::windows::fs::symlink_file(source, target),
+        #[cfg(not(any(unix, windows)))]
+        ConanDeployMethod::Symlink => std::fs::copy(source, target).map(|_| ()),
+    }
+}
@@ -0,0 +1,446 @@
+//! Conan command error reporting
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+/// Conan command invocation or output parsing error
+///
+/// Returned by the fallible [`ConanInstall::try_run()`](crate::ConanInstall::try_run),
+/// [`ConanLock::try_run()`](crate::ConanLock::try_run)
+/// and [`ConanOutput::try_parse()`](crate::ConanOutput::try_parse) methods
+/// and by the `bindgen` and `cmake` crate integration methods.
+#[derive(Debug)]
+pub enum ConanError {
+    /// The Conan executable could not be found or started.
+    ExecutableNotFound {
+        /// Conan executable name or path
+        program: OsString,
+        /// Underlying process spawning error
+        source: std::io::Error,
+    },
+    /// `conan profile detect` command failed.
+    ProfileDetectFailed {
+        /// Conan profile name, `None` for the default profile
+        profile: Option<String>,
+        /// `conan profile detect` command exit status
+        status: ExitStatus,
+    },
+    /// `conan install` or `conan lock create` command failed.
+    InstallFailed {
+        /// Conan command exit status code
+        code: i32,
+        /// Conan command error classification
+        kind: ConanErrorKind,
+        /// Conan command error message
+        stderr: String,
+    },
+    /// A file could not be read or written.
+    Io {
+        /// File path
+        path: PathBuf,
+        /// Underlying I/O error
+        source: std::io::Error,
+    },
+    /// The Conan command output is not valid JSON.
+    InvalidJson(serde_json::Error),
+    /// The Conan command JSON output does not match the expected schema.
+    UnexpectedSchema(&'static str),
+    /// The Conan package is not found in the dependency graph.
+    PackageNotFound(String),
+}
+
+/// `conan install` command failure classification
+///
+/// Classifies the most common `conan install` command failures
+/// parsed from the Conan error messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConanErrorKind {
+    /// `ERROR: Conanfile not found at {path}`
+    MissingConanfile {
+        /// Conan recipe file path
+        path: String,
+    },
+    /// `ERROR: Remote '{remote}' can't be found or is disabled`
+    UnknownRemote {
+        /// Conan remote name
+        remote: String,
+    },
+    /// `ERROR: Profile not found: {profile}`
+    MissingProfile {
+        /// Conan profile name
+        profile: String,
+    },
+    /// `error: unrecognized arguments: {arguments}`
+    UnrecognizedArguments {
+        /// Unrecognized command line arguments
+        arguments: String,
+    },
+    /// `ERROR: Missing prebuilt package for '{reference}'`
+    MissingBinary {
+        /// Conan package references with missing binaries
+        references: Vec<String>,
+    },
+    /// `ERROR: Version conflict: Conflict between {reference} and {existing} in the graph.`
+    VersionConflict {
+        /// Conflicting Conan package reference requirement
+        reference: String,
+        /// Conan package reference already in the dependency graph
+        existing: String,
+    },
+    /// Unclassified Conan error
+    Other {
+        /// The first Conan error message line
+        message: String,
+    },
+}
+
+impl ConanErrorKind {
+    /// Classifies the Conan command failure by parsing its error messages.
+    ///
+    /// Returns `None` if no error messages are found in the `stderr` output.
+    #[must_use]
+    pub fn from_stderr(stderr: &[u8]) -> Option<ConanErrorKind> {
+        let stderr = String::from_utf8_lossy(stderr);
+
+        let mut first_error = None;
+
+        for line in stderr.lines() {
+            if let Some((_, arguments)) = line.split_once("error: unrecognized arguments: ") {
+                return Some(ConanErrorKind::UnrecognizedArguments {
+                    arguments: arguments.to_owned(),
+                });
+            }
+
+            let Some(msg) = line
+                .strip_prefix("ERROR: ")
+                .or_else(|| line.split_once(": error: ").map(|(_, msg)| msg))
+            else {
+                continue;
+            };
+
+            if let Some(kind) = Self::from_message(msg) {
+                return Some(kind);
+            }
+
+            first_error.get_or_insert(msg);
+        }
+
+        first_error.map(|msg| ConanErrorKind::Other {
+            message: msg.to_owned(),
+        })
+    }
+
+    /// Classifies a single Conan error message line.
+    fn from_message(msg: &str) -> Option<ConanErrorKind> {
+        if let Some(path) = msg.strip_prefix("Conanfile not found at ") {
+            return Some(ConanErrorKind::MissingConanfile {
+                path: path.trim().to_owned(),
+            });
+        }
+
+        if let Some(profile) = msg.strip_prefix("Profile not found: ") {
+            return Some(ConanErrorKind::MissingProfile {
+                profile: profile.trim().to_owned(),
+            });
+        }
+
+        if let Some((remote, _)) = msg
+            .strip_prefix("Remote '")
+            .and_then(|msg| msg.split_once('\''))
+        {
+            return Some(ConanErrorKind::UnknownRemote {
+                remote: remote.to_owned(),
+            });
+        }
+
+        if let Some(refs) = msg.strip_prefix("Missing prebuilt package for ") {
+            // The reference list is terminated by the first unquoted period.
+            let refs = refs.split(". ").next().unwrap_or_default();
+
+            let references = refs
+                .split(',')
+                .map(|r| r.trim().trim_end_matches('.').trim_matches('\''))
+                .filter(|r| !r.is_empty())
+                .map(str::to_owned)
+                .collect();
+
+            return Some(ConanErrorKind::MissingBinary { references });
+        }
+
+        if let Some((reference, existing)) = msg
+            .strip_prefix("Version conflict: Conflict between ")
+            .and_then(|msg| msg.split_once(" and "))
+        {
+            let existing = existing.split_whitespace().next().unwrap_or_default();
+
+            return Some(ConanErrorKind::VersionConflict {
+                reference: reference.to_owned(),
+                existing: existing.to_owned(),
+            });
+        }
+
+        None
+    }
+}
+
+impl std::fmt::Display for ConanErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConanErrorKind::MissingConanfile { path } => {
+                write!(f, "Conan recipe file not found at '{path}'")
+            }
+            ConanErrorKind::UnknownRemote { remote } => {
+                write!(
+                    f,
+                    "Conan remote '{remote}' can't be found or is disabled, \
+                     check `conan remote list` output"
+                )
+            }
+            ConanErrorKind::MissingProfile { profile } => {
+                write!(
+                    f,
+                    "Conan profile '{profile}' not found, \
+                     use `ConanInstall::detect_profile()` to create it automatically"
+                )
+            }
+            ConanErrorKind::UnrecognizedArguments { arguments } => {
+                write!(f, "unrecognized 'conan install' arguments: {arguments}")
+            }
+            ConanErrorKind::MissingBinary { references } => {
+                write!(
+                    f,
+                    "missing prebuilt Conan package binaries for '{}', \
+                     use `ConanInstall::build(\"missing\")` to build them from sources",
+                    references.join("', '")
+                )
+            }
+            ConanErrorKind::VersionConflict {
+                reference,
+                existing,
+            } => {
+                write!(
+                    f,
+                    "Conan version conflict between '{reference}' and '{existing}'"
+                )
+            }
+            ConanErrorKind::Other { message } => f.write_str(message),
+        }
+    }
+}
+
+impl std::fmt::Display for ConanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConanError::ExecutableNotFound { program, source } => {
+                let program = program.to_string_lossy();
+                write!(
+                    f,
+                    "failed to run the Conan executable '{program}': {source}"
+                )
+            }
+            ConanError::ProfileDetectFailed {
+                profile: Some(profile),
+                status,
+            } => {
+                write!(
+                    f,
+                    "'conan profile detect' command failed for profile '{profile}': {status}"
+                )
+            }
+            ConanError::ProfileDetectFailed {
+                profile: None,
+                status,
+            } => write!(f, "'conan profile detect' command failed: {status}"),
+            ConanError::InstallFailed {
+                code,
+                kind: ConanErrorKind::Other { .. },
+                stderr,
+            } => write!(f, "Conan failed with status {code}: {stderr}"),
+            ConanError::InstallFailed { code, kind, .. } => {
+                write!(f, "Conan failed with status {code}: {kind}")
+            }
+            ConanError::Io { path, source } => {
+                write!(f, "failed to access '{}': {source}", path.display())
+            }
+            ConanError::InvalidJson(err) => write!(f, "failed to parse JSON output: {err}"),
+            ConanError::UnexpectedSchema(msg) => write!(f, "unexpected JSON output: {msg}"),
+            ConanError::PackageNotFound(package) => {
+                write!(f, "Conan package '{package}' not found")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConanError::ExecutableNotFound { source, .. } | ConanError::Io { source, .. } => {
+                Some(source)
+            }
+            ConanError::InvalidJson(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ConanError {
+    fn from(err: serde_json::Error) -> Self {
+        ConanError::InvalidJson(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConanErrorKind;
+
+    fn classify(stderr: &str) -> Option<ConanErrorKind> {
+        ConanErrorKind::from_stderr(stderr.as_bytes())
+    }
+
+    #[test]
+    fn missing_conanfile() {
+        let stderr = "ERROR: Conanfile not found at /work/conanfile.py\n";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::MissingConanfile {
+                path: "/work/conanfile.py".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_remote() {
+        let stderr = "\
+======== Input profiles ========
+ERROR: Remote 'nosuchremote' can't be found or is disabled
+";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::UnknownRemote {
+                remote: "nosuchremote".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn missing_profile() {
+        let stderr = "ERROR: Profile not found: linux-armv8\n";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::MissingProfile {
+                profile: "linux-armv8".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn unrecognized_arguments() {
+        let stderr = "\
+usage: conan install [-h] [-v [V]] [-cc CORE_CONF] [-f {json}] [--name NAME]
+conan install: error: unrecognized arguments: --frobnicate --max
+";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::UnrecognizedArguments {
+                arguments: "--frobnicate --max".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn missing_binary() {
+        let stderr = "\
+======== Computing necessary packages ========
+Requirements
+    zlib/1.3.1#b8bc2603263cf7eccbd6e17e66b0ed76:0d6dd492a7d31822b2f2686ec67bbaef586416a3 - Missing
+ERROR: Missing prebuilt package for 'zlib/1.3.1'. You can try:
+    - List all available packages using 'conan list \"zlib/1.3.1:*\" -r=remote'
+    - Try to build locally from sources using the '--build=zlib/1.3.1' argument
+";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::MissingBinary {
+                references: vec!["zlib/1.3.1".to_owned()]
+            })
+        );
+    }
+
+    #[test]
+    fn missing_binaries() {
+        let stderr = "\
+ERROR: Missing prebuilt package for 'libiconv/1.17', 'libxml2/2.15.0', 'zlib/1.3.1'. You can try:
+    - Try to build locally from sources using the '--build=libiconv/1.17 --build=libxml2/2.15.0 --build=zlib/1.3.1' argument
+";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::MissingBinary {
+                references: vec![
+                    "libiconv/1.17".to_owned(),
+                    "libxml2/2.15.0".to_owned(),
+                    "zlib/1.3.1".to_owned()
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn version_conflict() {
+        let stderr = "\
+======== Computing dependency graph ========
+Graph root
+    conanfile.txt: /work/conanfile.txt
+ERROR: Version conflict: Conflict between zlib/1.2.13 and zlib/1.3.1 in the graph.
+Conflict originates from libxml2/2.15.0
+";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::VersionConflict {
+                reference: "zlib/1.2.13".to_owned(),
+                existing: "zlib/1.3.1".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn other_error() {
+        let stderr = "\
+ERROR: Package 'nosuchpackage/1.0' not resolved: Unable to find 'nosuchpackage/1.0' in remotes
+ERROR: Another error
+";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::Other {
+                message: "Package 'nosuchpackage/1.0' not resolved: \
+                          Unable to find 'nosuchpackage/1.0' in remotes"
+                    .to_owned()
+            })
+        );
+
+        let stderr = "conanfile.py (foo/1.0): error: invalid configuration\n";
+
+        assert_eq!(
+            classify(stderr),
+            Some(ConanErrorKind::Other {
+                message: "invalid configuration".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn no_errors() {
+        assert_eq!(classify(""), None);
+        assert_eq!(
+            classify("WARN: deprecated: Usage of deprecated Conan 1.X features\n"),
+            None
+        );
+    }
+}
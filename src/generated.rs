@@ -0,0 +1,111 @@
+//! Conan generated files listing
+
+use std::path::{Path, PathBuf};
+
+use crate::ConanError;
+
+/// Conan `CMakeToolchain` generator toolchain file name
+pub(crate) const CMAKE_TOOLCHAIN_FILE: &str = "conan_toolchain.cmake";
+
+/// Files written by the Conan generators into the generators folder
+///
+/// Use [`CargoInstructions::generated_files()`](crate::CargoInstructions::generated_files)
+/// to list the files generated by `conan install`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConanGeneratedFiles {
+    /// Generators folder path
+    folder: Option<PathBuf>,
+    /// Generated file paths sorted by name
+    files: Vec<PathBuf>,
+}
+
+impl ConanGeneratedFiles {
+    /// Gets the generators folder path, if known.
+    #[must_use]
+    pub fn folder(&self) -> Option<&Path> {
+        self.folder.as_deref()
+    }
+
+    /// Gets all the generated file paths sorted by name.
+    #[must_use]
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Gets the generated file path by its file name.
+    #[must_use]
+    pub fn file(&self, name: &str) -> Option<&Path> {
+        self.files
+            .iter()
+            .find(|path| path.file_name().is_some_and(|n| n == name))
+            .map(PathBuf::as_path)
+    }
+
+    /// Gets the `conan_toolchain.cmake` file path written by
+    /// the `CMakeToolchain` generator.
+    #[must_use]
+    pub fn cmake_toolchain(&self) -> Option<&Path> {
+        self.file(CMAKE_TOOLCHAIN_FILE)
+    }
+
+    /// Gets the `*.pc` file paths written by the `PkgConfigDeps` generator.
+    #[must_use]
+    pub fn pkg_config_files(&self) -> Vec<&Path> {
+        self.with_extensions(&["pc"]).collect()
+    }
+
+    /// Gets the `conanbuild*` and `conanrun*` environment script paths
+    /// written by the `VirtualBuildEnv` and `VirtualRunEnv` generators.
+    #[must_use]
+    pub fn env_scripts(&self) -> Vec<&Path> {
+        self.with_extensions(&["sh", "bat", "ps1"])
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with("conanbuild") || name.starts_with("conanrun")
+                    })
+            })
+            .collect()
+    }
+
+    /// Lists the files in the generators folder.
+    pub(crate) fn read(folder: Option<&Path>) -> Result<ConanGeneratedFiles, ConanError> {
+        let Some(folder) = folder else {
+            return Ok(ConanGeneratedFiles::default());
+        };
+
+        let io_error = |source| ConanError::Io {
+            path: folder.to_owned(),
+            source,
+        };
+
+        let mut files = Vec::new();
+
+        for entry in std::fs::read_dir(folder).map_err(io_error)? {
+            let entry = entry.map_err(io_error)?;
+
+            if entry.file_type().map_err(io_error)?.is_file() {
+                files.push(entry.path());
+            }
+        }
+
+        files.sort();
+
+        Ok(ConanGeneratedFiles {
+            folder: Some(folder.to_owned()),
+            files,
+        })
+    }
+
+    /// Gets the generated file paths with any of the `extensions`.
+    fn with_extensions<'a>(&'a self, extensions: &'a [&str]) -> impl Iterator<Item = &'a Path> {
+        self.files
+            .iter()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| extensions.iter().any(|e| ext == *e))
+            })
+            .map(PathBuf::as_path)
+    }
+}
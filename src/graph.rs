@@ -0,0 +1,547 @@
+//! Conan dependency graph model
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::{ConanError, CppComponent};
+
+/// Conan dependency graph
+///
+/// The typed model of the dependency graph resolved by Conan
+/// as reported by `conan install --format json` or
+/// `conan graph info --format json` commands.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConanGraph {
+    /// Graph nodes sorted by their numeric ids
+    nodes: Vec<ConanNode>,
+    /// Root (consumer) node id
+    root_id: String,
+}
+
+/// Conan dependency graph node: the consumer or a dependency package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConanNode {
+    /// Node id
+    id: String,
+    /// Package reference: `name/version[@user/channel][#revision]`
+    reference: String,
+    /// Package name
+    name: Option<String>,
+    /// Package version
+    version: Option<String>,
+    /// Package user
+    user: Option<String>,
+    /// Package channel
+    channel: Option<String>,
+    /// Recipe revision
+    revision: Option<String>,
+    /// Binary package id
+    package_id: Option<String>,
+    /// Package type
+    package_type: ConanPackageType,
+    /// Dependency context
+    context: ConanContext,
+    /// Package settings
+    settings: BTreeMap<String, String>,
+    /// Package options
+    options: BTreeMap<String, String>,
+    /// Binary package status
+    binary: Option<ConanBinaryStatus>,
+    /// Binary package folder path
+    package_folder: Option<PathBuf>,
+    /// Generated files folder path (consumer only)
+    generators_folder: Option<PathBuf>,
+    /// Dependency edges
+    dependencies: Vec<ConanDependency>,
+    /// C/C++ package components sorted by their names
+    cpp_info: Vec<CppComponent>,
+}
+
+/// Conan dependency graph edge with its requirement traits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConanDependency {
+    /// Dependency node id
+    id: String,
+    /// Dependency package reference
+    reference: String,
+    /// `direct` requirement trait
+    direct: bool,
+    /// `build` requirement trait
+    build: bool,
+    /// `test` requirement trait
+    test: bool,
+    /// `run` requirement trait
+    run: bool,
+    /// `headers` requirement trait
+    headers: bool,
+    /// `libs` requirement trait
+    libs: bool,
+    /// `visible` requirement trait
+    visible: bool,
+}
+
+/// Conan dependency context
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConanContext {
+    /// `host` context: packages for the target platform
+    #[default]
+    Host,
+    /// `build` context: tools running on the build platform
+    Build,
+}
+
+/// Conan package type
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConanPackageType {
+    /// `application`
+    Application,
+    /// `library`: either static or shared depending on the options
+    Library,
+    /// `static-library`
+    StaticLibrary,
+    /// `shared-library`
+    SharedLibrary,
+    /// `header-library`
+    HeaderLibrary,
+    /// `build-scripts`
+    BuildScripts,
+    /// `python-require`
+    PythonRequire,
+    /// `unknown`
+    #[default]
+    Unknown,
+}
+
+/// Conan binary package status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConanBinaryStatus {
+    /// `Cache`: found in the local Conan cache
+    Cache,
+    /// `Download`: downloaded from a remote
+    Download,
+    /// `Update`: updated from a remote
+    Update,
+    /// `Build`: built from sources
+    Build,
+    /// `Missing`: no binary package available
+    Missing,
+    /// `Invalid`: the configuration is not supported by the package
+    Invalid,
+    /// `Skip`: the binary package is not needed
+    Skip,
+    /// `Editable`: an editable package
+    Editable,
+    /// `EditableBuild`: an editable package built from sources
+    EditableBuild,
+    /// `Platform`: provided by the platform
+    Platform,
+}
+
+impl ConanGraph {
+    /// Parses the JSON-formatted Conan dependency graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON-formatted Conan dependency graph
+    /// could not be parsed.
+    pub fn from_json(json: &[u8]) -> Result<ConanGraph, ConanError> {
+        let metadata: Value = serde_json::from_slice(json)?;
+
+        Self::from_value(&metadata)
+    }
+
+    /// Gets all the graph nodes sorted by their ids.
+    #[must_use]
+    pub fn nodes(&self) -> &[ConanNode] {
+        &self.nodes
+    }
+
+    /// Gets the root (consumer) node of the graph.
+    #[must_use]
+    pub fn root(&self) -> Option<&ConanNode> {
+        self.node(&self.root_id)
+    }
+
+    /// Gets the graph node by its `id`.
+    #[must_use]
+    pub fn node(&self, id: &str) -> Option<&ConanNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Gets the host context package node by the package `name`.
+    #[must_use]
+    pub fn package(&self, name: &str) -> Option<&ConanNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.context == ConanContext::Host && node.name() == Some(name))
+    }
+
+    /// Validates the JSON-formatted Conan dependency graph schema
+    /// and converts it into the typed graph model.
+    fn from_value(metadata: &Value) -> Result<ConanGraph, ConanError> {
+        let Value::Object(root) = metadata else {
+            return Err(ConanError::UnexpectedSchema("root JSON object expected"));
+        };
+
+        let Some(Value::Object(graph)) = root.get("graph") else {
+            return Err(ConanError::UnexpectedSchema("root 'graph' object expected"));
+        };
+
+        let Some(Value::Object(nodes)) = graph.get("nodes") else {
+            return Err(ConanError::UnexpectedSchema("root 'nodes' object expected"));
+        };
+
+        let mut nodes: Vec<ConanNode> = nodes
+            .iter()
+            .filter_map(|(id, node)| match node {
+                Value::Object(node) => Some(ConanNode::from_map(id, node)),
+                _ => None,
+            })
+            .collect();
+
+        nodes.sort_by(|a, b| node_order(&a.id).cmp(&node_order(&b.id)));
+
+        // The consumer node is "0" unless specified otherwise.
+        let root_id = match graph.get("root") {
+            Some(Value::Object(root)) => root.keys().next().cloned(),
+            _ => None,
+        };
+
+        Ok(ConanGraph {
+            nodes,
+            root_id: root_id.unwrap_or_else(|| "0".to_owned()),
+        })
+    }
+}
+
+impl ConanNode {
+    /// Gets the graph node id.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Gets the package reference (the `ref` field),
+    /// e.g. `zlib/1.3.1#b8bc2603263cf7eccbd6e17e66b0ed76`.
+    #[must_use]
+    pub fn ref_(&self) -> &str {
+        &self.reference
+    }
+
+    /// Gets the package name.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the package version.
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Gets the package user.
+    #[must_use]
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Gets the package channel.
+    #[must_use]
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    /// Gets the package recipe revision.
+    #[must_use]
+    pub fn revision(&self) -> Option<&str> {
+        self.revision.as_deref()
+    }
+
+    /// Gets the binary package id.
+    #[must_use]
+    pub fn package_id(&self) -> Option<&str> {
+        self.package_id.as_deref()
+    }
+
+    /// Gets the package type.
+    #[must_use]
+    pub fn package_type(&self) -> ConanPackageType {
+        self.package_type
+    }
+
+    /// Gets the dependency context: host or build.
+    #[must_use]
+    pub fn context(&self) -> ConanContext {
+        self.context
+    }
+
+    /// Gets the package settings.
+    #[must_use]
+    pub fn settings(&self) -> &BTreeMap<String, String> {
+        &self.settings
+    }
+
+    /// Gets the package option values.
+    #[must_use]
+    pub fn options(&self) -> &BTreeMap<String, String> {
+        &self.options
+    }
+
+    /// Gets the binary package status.
+    #[must_use]
+    pub fn binary(&self) -> Option<ConanBinaryStatus> {
+        self.binary
+    }
+
+    /// Gets the binary package folder path.
+    #[must_use]
+    pub fn package_folder(&self) -> Option<&Path> {
+        self.package_folder.as_deref()
+    }
+
+    /// Gets the folder path the generated files are written to.
+    ///
+    /// Only set for the consumer (root) node.
+    #[must_use]
+    pub fn generators_folder(&self) -> Option<&Path> {
+        self.generators_folder.as_deref()
+    }
+
+    /// Gets the dependency edges: both direct and transitive.
+    #[must_use]
+    pub fn dependencies(&self) -> &[ConanDependency] {
+        &self.dependencies
+    }
+
+    /// Gets the C/C++ package components sorted by their names.
+    ///
+    /// The packages without components have a single `root` component.
+    #[must_use]
+    pub fn cpp_info(&self) -> &[CppComponent] {
+        &self.cpp_info
+    }
+
+    /// Gets the C/C++ package component by its `name`.
+    #[must_use]
+    pub fn component(&self, name: &str) -> Option<&CppComponent> {
+        self.cpp_info
+            .iter()
+            .find(|component| component.name() == name)
+    }
+
+    /// Converts the JSON graph node object into the typed graph node.
+    fn from_map(id: &str, node: &Map<String, Value>) -> ConanNode {
+        let dependencies = match node.get("dependencies") {
+            Some(Value::Object(dependencies)) => dependencies
+                .iter()
+                .filter_map(|(id, dependency)| match dependency {
+                    Value::Object(dependency) => Some(ConanDependency::from_map(id, dependency)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let cpp_info = match node.get("cpp_info") {
+            Some(Value::Object(cpp_info)) => cpp_info
+                .iter()
+                .filter_map(|(name, component)| match component {
+                    Value::Object(component) => Some(CppComponent::from_map(name, component)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        ConanNode {
+            id: id.to_owned(),
+            reference: get_string(node, "ref").unwrap_or_default(),
+            name: get_string(node, "name"),
+            version: get_string(node, "version"),
+            user: get_string(node, "user"),
+            channel: get_string(node, "channel"),
+            revision: get_string(node, "rrev"),
+            package_id: get_string(node, "package_id"),
+            package_type: get_string(node, "package_type")
+                .map(|t| ConanPackageType::from_str(&t))
+                .unwrap_or_default(),
+            context: match node.get("context").and_then(Value::as_str) {
+                Some("build") => ConanContext::Build,
+                _ => ConanContext::Host,
+            },
+            settings: get_string_map(node, "settings"),
+            options: get_string_map(node, "options"),
+            binary: get_string(node, "binary").and_then(|b| ConanBinaryStatus::from_str(&b)),
+            package_folder: get_string(node, "package_folder").map(PathBuf::from),
+            generators_folder: get_string(node, "generators_folder").map(PathBuf::from),
+            dependencies,
+            cpp_info,
+        }
+    }
+}
+
+impl ConanDependency {
+    /// Gets the dependency graph node id.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Gets the dependency package reference (the `ref` field).
+    #[must_use]
+    pub fn ref_(&self) -> &str {
+        &self.reference
+    }
+
+    /// Checks if this is a direct dependency of the node.
+    #[must_use]
+    pub fn direct(&self) -> bool {
+        self.direct
+    }
+
+    /// Checks if this is a build requirement (tool requirement).
+    #[must_use]
+    pub fn build(&self) -> bool {
+        self.build
+    }
+
+    /// Checks if this is a test requirement.
+    #[must_use]
+    pub fn test(&self) -> bool {
+        self.test
+    }
+
+    /// Checks if the dependency executables are needed at runtime.
+    #[must_use]
+    pub fn run(&self) -> bool {
+        self.run
+    }
+
+    /// Checks if the dependency headers are used.
+    #[must_use]
+    pub fn headers(&self) -> bool {
+        self.headers
+    }
+
+    /// Checks if the dependency libraries are linked.
+    #[must_use]
+    pub fn libs(&self) -> bool {
+        self.libs
+    }
+
+    /// Checks if the dependency is visible to the downstream consumers.
+    #[must_use]
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Converts the JSON dependency object into the typed graph edge.
+    fn from_map(id: &str, dependency: &Map<String, Value>) -> ConanDependency {
+        let get_bool = |key| dependency.get(key).and_then(Value::as_bool);
+
+        ConanDependency {
+            id: id.to_owned(),
+            reference: get_string(dependency, "ref").unwrap_or_default(),
+            direct: get_bool("direct").unwrap_or(true),
+            build: get_bool("build").unwrap_or(false),
+            test: get_bool("test").unwrap_or(false),
+            run: get_bool("run").unwrap_or(false),
+            headers: get_bool("headers").unwrap_or(true),
+            libs: get_bool("libs").unwrap_or(true),
+            visible: get_bool("visible").unwrap_or(true),
+        }
+    }
+}
+
+impl ConanPackageType {
+    /// Parses the Conan `package_type` attribute value.
+    pub(crate) fn from_str(package_type: &str) -> ConanPackageType {
+        match package_type {
+            "application" => ConanPackageType::Application,
+            "library" => ConanPackageType::Library,
+            "static-library" => ConanPackageType::StaticLibrary,
+            "shared-library" => ConanPackageType::SharedLibrary,
+            "header-library" => ConanPackageType::HeaderLibrary,
+            "build-scripts" => ConanPackageType::BuildScripts,
+            "python-require" => ConanPackageType::PythonRequire,
+            _ => ConanPackageType::Unknown,
+        }
+    }
+}
+
+impl ConanBinaryStatus {
+    /// Parses the Conan `binary` attribute value.
+    fn from_str(binary: &str) -> Option<ConanBinaryStatus> {
+        let status = match binary {
+            "Cache" => ConanBinaryStatus::Cache,
+            "Download" => ConanBinaryStatus::Download,
+            "Update" => ConanBinaryStatus::Update,
+            "Build" => ConanBinaryStatus::Build,
+            "Missing" => ConanBinaryStatus::Missing,
+            "Invalid" => ConanBinaryStatus::Invalid,
+            "Skip" => ConanBinaryStatus::Skip,
+            "Editable" => ConanBinaryStatus::Editable,
+            "EditableBuild" => ConanBinaryStatus::EditableBuild,
+            "Platform" => ConanBinaryStatus::Platform,
+            _ => return None,
+        };
+
+        Some(status)
+    }
+}
+
+impl std::fmt::Display for ConanContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConanContext::Host => f.write_str("host"),
+            ConanContext::Build => f.write_str("build"),
+        }
+    }
+}
+
+impl std::fmt::Display for ConanPackageType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConanPackageType::Application => f.write_str("application"),
+            ConanPackageType::Library => f.write_str("library"),
+            ConanPackageType::StaticLibrary => f.write_str("static-library"),
+            ConanPackageType::SharedLibrary => f.write_str("shared-library"),
+            ConanPackageType::HeaderLibrary => f.write_str("header-library"),
+            ConanPackageType::BuildScripts => f.write_str("build-scripts"),
+            ConanPackageType::PythonRequire => f.write_str("python-require"),
+            ConanPackageType::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Gets the node ordering key: numeric ids first, in numeric order.
+fn node_order(id: &str) -> (u64, &str) {
+    (id.parse().unwrap_or(u64::MAX), id)
+}
+
+/// Gets the string value of the JSON object field.
+pub(crate) fn get_string(map: &Map<String, Value>, key: &str) -> Option<String> {
+    match map.get(key) {
+        Some(Value::String(value)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+/// Gets the JSON object field as a string map skipping the `null` values.
+pub(crate) fn get_string_map(map: &Map<String, Value>, key: &str) -> BTreeMap<String, String> {
+    let Some(Value::Object(values)) = map.get(key) else {
+        return BTreeMap::new();
+    };
+
+    values
+        .iter()
+        .filter_map(|(key, value)| match value {
+            Value::Null => None,
+            Value::String(value) => Some((key.clone(), value.clone())),
+            value => Some((key.clone(), value.to_string())),
+        })
+        .collect()
+}
@@ -76,6 +76,38 @@
 //!     .emit();
 //! ```
 //!
+//! ### Automatic Conan settings inference from Cargo target
+//!
+//! Cross-compiling without custom Conan profiles by translating the Cargo target
+//! information into the Conan host `os`, `os.subsystem` and `arch` settings:
+//!
+//! ```no_run
+//! use conan2::ConanInstall;
+//!
+//! ConanInstall::new()
+//!     .settings_from_target() // Adds `--settings:host os=Linux arch=armv8` etc.
+//!     .build("missing")
+//!     .run()
+//!     .parse()
+//!     .emit();
+//! ```
+//!
+//! ### Generating the Conan host profile from the C/C++ toolchain
+//!
+//! Cross-compiling with the same C/C++ compilers as the `cc` crate uses
+//! (configured via `CC`, `CXX`, `TARGET_CC` etc. environment variables):
+//!
+//! ```no_run
+//! use conan2::ConanInstall;
+//!
+//! ConanInstall::new()
+//!     .generate_host_profile() // Writes and uses `$OUT_DIR/conan_host_profile`
+//!     .build("missing")
+//!     .run()
+//!     .parse()
+//!     .emit();
+//! ```
+//!
 //! ### Automatic Conan profile creation
 //!
 //! Creating a custom default Conan profile on the fly with zero configuration:
@@ -107,9 +139,154 @@
 //!     .emit();
 //! ```
 //!
-//! ### Getting C/C++ include paths from Conan dependencies
+//! ### Using inline Conan profiles
+//!
+//! Defining the Conan profiles in the build script instead of `~/.conan2/profiles`:
+//!
+//! ```no_run
+//! use conan2::{ConanInstall, ConanProfile, ConanScope};
+//!
+//! let mut profile = ConanProfile::new();
+//! profile
+//!     .include("default")
+//!     .setting("compiler.cppstd", "17")
+//!     .option(ConanScope::Global, "shared", "False")
+//!     .conf("tools.build:skip_test", "True")
+//!     .tool_require("cmake/3.30.1");
+//!
+//! ConanInstall::new()
+//!     .host_profile_inline(&profile) // Writes and uses `$OUT_DIR/conan_host_inline_profile`
+//!     .run()
+//!     .parse()
+//!     .emit();
+//! ```
+//!
+//! ### Using Conan lockfiles
+//!
+//! Pinning the Conan dependencies to the exact recipe revisions
+//! with a lockfile created by `conan lock create`:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use conan2::{ConanInstall, ConanLock};
+//!
+//! let lockfile = Path::new("conan.lock");
+//! let mut install = ConanInstall::new();
+//!
+//! if !lockfile.exists() {
+//!     ConanLock::new(&install).lockfile_out(lockfile).run();
+//! }
+//!
+//! install
+//!     .lockfile(lockfile) // Emits `cargo:rerun-if-changed=conan.lock`
+//!     .run()
+//!     .parse()
+//!     .emit();
+//! ```
+//!
+//! ### Using a saved Conan dependency graph
+//!
+//! Linking the dependencies described by a `conan install --format json`
+//! or `conan graph info --format json` output saved earlier, without running Conan:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use conan2::ConanOutput;
+//!
+//! ConanOutput::from_json_file(Path::new("conan-graph.json"))
+//!     .expect("failed to read the saved Conan graph")
+//!     .parse()
+//!     .emit();
+//! ```
+//!
+//! ### Inspecting the resolved Conan dependency graph
+//!
+//! Making build script decisions based on the packages Conan actually resolved:
+//!
+//! ```no_run
+//! use conan2::{ConanContext, ConanInstall, ConanPackageType};
+//!
+//! let metadata = ConanInstall::new().run().parse();
+//!
+//! for node in metadata.graph().nodes() {
+//!     if node.context() == ConanContext::Host
+//!         && node.package_type() == ConanPackageType::SharedLibrary
+//!     {
+//!         println!("cargo:warning={} is a shared library", node.ref_());
+//!     }
+//! }
+//!
+//! metadata.emit();
+//! ```
+//!
+//! ### Controlling the C/C++ library link kind
+//!
+//! The `static=` or `dylib=` link kind is inferred from the Conan package type and
+//! the `shared` package option. It can be overridden per package if necessary:
+//!
+//! ```no_run
+//! use conan2::{ConanInstall, ConanLinkKind, ConanLinkOptions};
+//!
+//! let mut options = ConanLinkOptions::new();
+//! options.link_kind("openssl", ConanLinkKind::Dylib);
+//!
+//! ConanInstall::new()
+//!     .run()
+//!     .parse_with(&options)
+//!     .emit();
+//! ```
+//!
+//! ### Linking only the selected package components
+//!
+//! The libraries are linked in the dependency order, each one once. Only the required
+//! components of the multi-component packages are linked, as listed in the component
+//! `requires`. The packages required as a whole, like Boost, can be narrowed down
+//! to a subset of their components and the transitive component requirements:
+//!
+//! ```no_run
+//! use conan2::{ConanInstall, ConanLinkOptions};
+//!
+//! let mut options = ConanLinkOptions::new();
+//! options.components("boost", &["filesystem", "system"]);
+//!
+//! ConanInstall::new()
+//!     .run()
+//!     .parse_with(&options)
+//!     .emit();
+//! ```
+//!
+//! ### Excluding or replacing Conan packages at link time
+//!
+//! Resolving a package with Conan for its headers only, dropping a package altogether,
+//! or renaming, re-kinding and skipping the individual libraries before their
+//! `cargo:rustc-link-lib` instructions are emitted:
+//!
+//! ```no_run
+//! use conan2::{ConanInstall, ConanLinkKind, ConanLinkOptions};
+//!
+//! let mut options = ConanLinkOptions::new();
+//! options
+//!     .headers_only("openssl") // Link the system OpenSSL instead
+//!     .exclude_package("zlib")
+//!     .on_library(|decision| {
+//!         if decision.package() == "libxml2" && decision.is_system() {
+//!             decision.skip();
+//!         } else if decision.name() == "bz2" {
+//!             decision.rename("bzip2").set_kind(Some(ConanLinkKind::Dylib));
+//!         }
+//!     });
+//!
+//! ConanInstall::new()
+//!     .run()
+//!     .parse_with(&options)
+//!     .emit();
+//! ```
+//!
+//! ### Getting C/C++ include paths and flags from Conan dependencies
 //!
-//! To use the list of include paths, do the following after
+//! To use the list of include paths and preprocessor definitions, do the following after
 //! parsing the `conan install` output:
 //!
 //! ```no_run
@@ -121,18 +298,219 @@
 //!     // Add "-I{path}" to CXXFLAGS or something.
 //! }
 //!
+//! for define in metadata.defines() {
+//!     // Add "-D{define}" to CFLAGS and CXXFLAGS.
+//! }
+//!
+//! // Also see `metadata.cflags()` and `metadata.cxxflags()`.
+//!
+//! metadata.emit();
+//! ```
+//!
+//! ### Compiling C/C++ glue code with the `cc` crate
+//!
+//! Enable the optional `cc` feature to configure a `cc::Build` with the include paths,
+//! preprocessor definitions and compiler flags of all Conan dependencies:
+//!
+//! ```toml
+//! [build-dependencies]
+//! cc = "1.0"
+//! conan2 = { version = "0.1", features = ["cc"] }
+//! ```
+//!
+//! ```ignore
+//! use conan2::ConanInstall;
+//!
+//! let metadata = ConanInstall::new().run().parse();
+//!
+//! metadata
+//!     .configure_cc(&mut cc::Build::new()) // Use `configure_cxx()` for C++ code
+//!     .file("src/glue.c")
+//!     .compile("glue");
+//!
+//! metadata.emit();
+//! ```
+//!
+//! ### Generating Rust bindings with `bindgen`
+//!
+//! Enable the optional `bindgen` feature to generate the bindings for a Conan package
+//! headers using the include paths and preprocessor definitions from the dependency graph:
+//!
+//! ```toml
+//! [build-dependencies]
+//! bindgen = "0.72"
+//! conan2 = { version = "0.1", features = ["bindgen"] }
+//! ```
+//!
+//! ```ignore
+//! use conan2::ConanInstall;
+//!
+//! let metadata = ConanInstall::new().run().parse();
+//!
+//! metadata
+//!     .configure_bindgen_package(bindgen::builder(), "libxml2") // Only libxml2 headers
+//!     .expect("libxml2 is not a Conan dependency")
+//!     .header("src/libxml2.h")
+//!     .generate()
+//!     .expect("failed to generate libxml2 bindings")
+//!     .write_to_file(std::env::var("OUT_DIR").unwrap() + "/libxml2.rs")
+//!     .unwrap();
+//!
+//! metadata.emit();
+//! ```
+//!
+//! ### Using Conan generators and deployers
+//!
+//! Requesting extra Conan generators and deployers and consuming the generated files:
+//!
+//! ```no_run
+//! use conan2::ConanInstall;
+//!
+//! let metadata = ConanInstall::new()
+//!     .generator("PkgConfigDeps") // Writes `*.pc` files into the output folder
+//!     .deployer("full_deploy") // Copies the dependency packages
+//!     .run()
+//!     .parse();
+//!
+//! let files = metadata.generated_files().expect("failed to list Conan generated files");
+//!
+//! for pc_file in files.pkg_config_files() {
+//!     println!("cargo:warning=pkg-config file: {}", pc_file.display());
+//! }
+//!
+//! metadata.emit();
+//! ```
+//!
+//! ### Running binaries linked against shared Conan packages
+//!
+//! Deploying the shared libraries from the Conan cache into the Cargo target
+//! profile directory (e.g. `target/debug`) to make `cargo run` and `cargo test` work:
+//!
+//! ```no_run
+//! use conan2::{ConanDeployMethod, ConanInstall, ConanScope};
+//!
+//! let metadata = ConanInstall::new()
+//!     .option(ConanScope::Global, "shared", "True")
+//!     .run()
+//!     .parse();
+//!
+//! let deployed = metadata
+//!     .deploy_runtime_libs(ConanDeployMethod::Symlink) // Or `ConanDeployMethod::Copy`
+//!     .expect("failed to deploy the shared libraries");
+//!
+//! for path in deployed {
+//!     println!("cargo:warning=deployed {}", path.display());
+//! }
+//!
+//! metadata.emit();
+//! ```
+//!
+//! Alternatively, use the Conan `runtime_deploy` deployer with
+//! `ConanInstall::deployer("runtime_deploy")` and `ConanInstall::deployer_folder()`.
+//!
+//! Or embedding the runtime library search paths (rpath) into the binaries instead:
+//!
+//! ```no_run
+//! use conan2::{ConanInstall, ConanRpath, ConanScope};
+//!
+//! ConanInstall::new()
+//!     .option(ConanScope::Global, "shared", "True")
+//!     .run()
+//!     .parse()
+//!     .rpath(ConanRpath::Absolute) // Emits `-Wl,-rpath,{libdir}` for the shared packages
+//!     .emit();
+//! ```
+//!
+//! ### Building bundled C/C++ code with the `cmake` crate
+//!
+//! Enable the optional `cmake` feature to build a CMake project against the Conan
+//! dependencies using the Conan-generated CMake toolchain and package config files:
+//!
+//! ```toml
+//! [build-dependencies]
+//! cmake = "0.1"
+//! conan2 = { version = "0.1", features = ["cmake"] }
+//! ```
+//!
+//! ```ignore
+//! use conan2::ConanInstall;
+//!
+//! let metadata = ConanInstall::new()
+//!     .generator("CMakeToolchain") // Writes `conan_toolchain.cmake`
+//!     .generator("CMakeDeps") // Writes `find_package()` config files
+//!     .run()
+//!     .parse();
+//!
+//! let dst = metadata
+//!     .configure_cmake(&mut cmake::Config::new("cpp"))
+//!     .expect("Conan generators folder is not known")
+//!     .build();
+//!
+//! println!("cargo:rustc-link-search=native={}/lib", dst.display());
 //! metadata.emit();
 //! ```
+//!
+//! ### Handling Conan errors
+//!
+//! Using the non-panicking API to fall back to the system libraries
+//! when Conan is not available or `conan install` fails:
+//!
+//! ```no_run
+//! use conan2::{ConanInstall, ConanOutput};
+//!
+//! match ConanInstall::new().try_run().and_then(ConanOutput::try_parse) {
+//!     Ok(metadata) => metadata.emit(),
+//!     Err(err) => {
+//!         println!("cargo:warning=Conan is not available: {err}");
+//!         // Probe the system libraries with `pkg-config` instead.
+//!     }
+//! }
+//! ```
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "bindgen")]
+mod bindgen_builder;
+mod cache;
+#[cfg(feature = "cc")]
+mod cc_build;
+#[cfg(feature = "cmake")]
+mod cmake_config;
+mod cpp_info;
+mod deploy;
+mod error;
+mod generated;
+mod graph;
+mod link;
+mod lock;
+mod order;
+mod profile;
+mod rpath;
+mod target;
+mod toolchain;
+
 use std::collections::BTreeSet;
-use std::ffi::OsStr;
-use std::io::{BufRead, Cursor, Write};
+use std::ffi::{OsStr, OsString};
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
-
-use serde_json::{Map, Value};
+use std::process::{Command, ExitStatus, Output};
+
+pub use cpp_info::CppComponent;
+pub use deploy::ConanDeployMethod;
+pub use error::{ConanError, ConanErrorKind};
+pub use generated::ConanGeneratedFiles;
+pub use graph::{
+    ConanBinaryStatus, ConanContext, ConanDependency, ConanGraph, ConanNode, ConanPackageType,
+};
+pub use link::{ConanLinkDecision, ConanLinkKind, ConanLinkOptions};
+pub use lock::ConanLock;
+pub use profile::ConanProfile;
+pub use rpath::ConanRpath;
+
+use cache::{Fingerprint, InstallCache};
+use link::{LinkLibrary, TargetFamily};
+use target::TargetSettings;
+use toolchain::HostToolchain;
 
 /// Conan binary override environment variable
 const CONAN_ENV: &str = "CONAN";
@@ -140,6 +518,36 @@ const CONAN_ENV: &str = "CONAN";
 /// Default Conan binary name
 const DEFAULT_CONAN: &str = "conan";
 
+/// Conan home directory override environment variable
+const CONAN_HOME_ENV: &str = "CONAN_HOME";
+
+/// Conan default host profile override environment variable
+const CONAN_DEFAULT_PROFILE_ENV: &str = "CONAN_DEFAULT_PROFILE";
+
+/// Conan default build profile override environment variable
+const CONAN_DEFAULT_BUILD_PROFILE_ENV: &str = "CONAN_DEFAULT_BUILD_PROFILE";
+
+/// Default Conan profile name
+const DEFAULT_PROFILE: &str = "default";
+
+/// Forced `conan install` re-run environment variable
+const FORCE_INSTALL_ENV: &str = "CONAN2_FORCE_INSTALL";
+
+/// Cargo build profile environment variable
+const PROFILE_ENV: &str = "PROFILE";
+
+/// Cargo target triple environment variable
+const TARGET_ENV: &str = "TARGET";
+
+/// Generated Conan host profile file name
+const HOST_PROFILE_FILE: &str = "conan_host_profile";
+
+/// Inline Conan host profile file name
+const HOST_INLINE_PROFILE_FILE: &str = "conan_host_inline_profile";
+
+/// Inline Conan build profile file name
+const BUILD_INLINE_PROFILE_FILE: &str = "conan_build_inline_profile";
+
 /// `conan` command verbosity level
 ///
 /// Defines the level of detail of the Conan command output.
@@ -187,23 +595,33 @@ pub enum ConanScope<'a> {
 ///
 /// This opaque type implements a command line builder for
 /// the `conan install` command invocation.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ConanInstall {
     /// Conan generators output directory
     output_folder: Option<PathBuf>,
     /// Conan recipe file path
     recipe_path: Option<PathBuf>,
-    /// Conan host profile name
-    profile: Option<String>,
-    /// Conan build profile name
-    build_profile: Option<String>,
+    /// Conan host profile
+    profile: Option<ProfileSource>,
+    /// Conan build profile
+    build_profile: Option<ProfileSource>,
     /// Conan profile auto-detection flag
     new_profile: bool,
+    /// Conan host profile generation from the C/C++ toolchain flag
+    toolchain_profile: bool,
     /// Conan build policy
     build: Option<String>,
+    /// Conan generator names
+    generators: Vec<String>,
+    /// Conan deployer names or paths
+    deployers: Vec<String>,
+    /// Conan deployer output folder
+    deployer_folder: Option<PathBuf>,
     /// Conan build type setting:
     /// one of "Debug", "Release", "RelWithDebInfo" and "MinSizeRel"
     build_type: Option<String>,
+    /// Conan host settings inference from the Cargo target flag
+    target_settings: bool,
     /// Custom remote name
     remote: Option<String>,
     /// Conan conf options stored as `{key}={value}`
@@ -212,12 +630,38 @@ pub struct ConanInstall {
     options: Vec<(String, String, String)>,
     /// Conan output verbosity level
     verbosity: ConanVerbosity,
+    /// Conan lockfile path
+    lockfile: Option<PathBuf>,
+    /// Updated Conan lockfile output path
+    lockfile_out: Option<PathBuf>,
+    /// Partial Conan lockfile flag
+    lockfile_partial: bool,
+    /// Clean Conan lockfile output flag
+    lockfile_clean: bool,
+    /// `conan install` output caching flag
+    cache: bool,
     /// Extra `conan install` arguments
     extra_args: Vec<String>,
 }
 
+/// Conan profile specified either by name or inline
+#[derive(Clone, PartialEq, Eq)]
+enum ProfileSource {
+    /// Conan profile name or file path
+    Named(String),
+    /// Inline Conan profile written into the output folder
+    Inline(ConanProfile),
+}
+
 /// `conan install` command output data
-pub struct ConanOutput(Output);
+pub struct ConanOutput {
+    /// Conan command process output
+    output: Output,
+    /// Conan input file paths for `cargo:rerun-if-changed` instructions
+    rerun_paths: Vec<PathBuf>,
+    /// Environment variables for `cargo:rerun-if-env-changed` instructions
+    rerun_envs: Vec<String>,
+}
 
 /// Build script instructions for Cargo
 pub struct CargoInstructions {
@@ -227,11 +671,20 @@ pub struct CargoInstructions {
     includes: BTreeSet<PathBuf>,
     /// C library search paths collected from the packages
     lib_dirs: BTreeSet<PathBuf>,
+    /// C/C++ preprocessor definitions collected from the packages
+    defines: Vec<String>,
+    /// C compiler flags collected from the packages
+    cflags: Vec<String>,
+    /// C++ compiler flags collected from the packages
+    cxxflags: Vec<String>,
+    /// Target platform library file naming conventions
+    target_family: TargetFamily,
+    /// Conan dependency graph the instructions are generated from
+    graph: ConanGraph,
+    /// C/C++ library linking options the instructions are generated with
+    link_options: ConanLinkOptions,
 }
 
-/// Conan dependency graph as a JSON-based tree structure
-struct ConanDependencyGraph(Value);
-
 impl std::fmt::Display for ConanVerbosity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -298,7 +751,7 @@ impl ConanInstall {
     ///
     /// Matches `--profile` Conan executable option.
     pub fn profile(&mut self, profile: &str) -> &mut ConanInstall {
-        self.profile = Some(profile.to_owned());
+        self.profile = Some(ProfileSource::Named(profile.to_owned()));
         self
     }
 
@@ -313,18 +766,66 @@ impl ConanInstall {
     ///
     /// Matches `--profile:build` Conan executable option.
     pub fn build_profile(&mut self, profile: &str) -> &mut ConanInstall {
-        self.build_profile = Some(profile.to_owned());
+        self.build_profile = Some(ProfileSource::Named(profile.to_owned()));
+        self
+    }
+
+    /// Sets the inline Conan host profile to use for installing dependencies.
+    ///
+    /// The profile is written into the output folder and its path
+    /// is passed to `conan install` via `--profile:host` option.
+    pub fn host_profile_inline(&mut self, profile: &ConanProfile) -> &mut ConanInstall {
+        self.profile = Some(ProfileSource::Inline(profile.clone()));
+        self
+    }
+
+    /// Sets the inline Conan build profile to use for installing dependencies.
+    ///
+    /// The profile is written into the output folder and its path
+    /// is passed to `conan install` via `--profile:build` option.
+    pub fn build_profile_inline(&mut self, profile: &ConanProfile) -> &mut ConanInstall {
+        self.build_profile = Some(ProfileSource::Inline(profile.clone()));
         self
     }
 
     /// Auto-detects and creates the Conan profile to use for installing dependencies.
     ///
     /// Schedules `conan profile detect --exist-ok` to run before running `conan install`.
+    ///
+    /// NOTE: The default profile is auto-detected for the inline profiles,
+    /// which commonly include it with `include(default)`.
     pub fn detect_profile(&mut self) -> &mut ConanInstall {
         self.new_profile = true;
         self
     }
 
+    /// Generates the Conan host profile describing the C/C++ toolchain
+    /// used by Cargo for the current target.
+    ///
+    /// The profile file is written into the output folder and passed
+    /// to `conan install` via `--profile:host` option.
+    ///
+    /// The generated profile defines:
+    ///
+    /// - `os`, `os.subsystem` and `arch` settings inferred from the Cargo target
+    ///   (see [`ConanInstall::settings_from_target()`]);
+    /// - `build_type` setting;
+    /// - `compiler`, `compiler.version`, `compiler.libcxx` and `compiler.cppstd`
+    ///   settings detected by running the C++ compiler;
+    /// - `CC`, `CXX` and `AR` environment variables in the `[buildenv]` section;
+    /// - `tools.build:compiler_executables` Conan configuration.
+    ///
+    /// The C/C++ compilers are found using the `CC`, `CXX` and `AR` environment
+    /// variables and their `TARGET_` and target-specific variants,
+    /// the same way it is done by the `cc` crate.
+    ///
+    /// The generated profile is based on the host profile set by
+    /// [`ConanInstall::profile()`] or the Conan default profile otherwise.
+    pub fn generate_host_profile(&mut self) -> &mut ConanInstall {
+        self.toolchain_profile = true;
+        self
+    }
+
     /// Overrides the default Conan build type setting value for `conan install`.
     ///
     /// Matches `--settings build_type={value}` Conan executable option.
@@ -337,6 +838,65 @@ impl ConanInstall {
         self
     }
 
+    /// Infers the Conan host `os`, `os.subsystem` and `arch` settings
+    /// from the Cargo target information.
+    ///
+    /// Matches `--settings:host {key}={value}` Conan executable options.
+    ///
+    /// The settings are derived from the `TARGET` and `CARGO_CFG_TARGET_OS`
+    /// environment variables set by Cargo for the build scripts.
+    /// Nothing is added when these variables are not defined or
+    /// the target can not be mapped to the Conan settings.
+    ///
+    /// | Cargo `target_os`     | Conan `os`             |
+    /// |-----------------------|------------------------|
+    /// | `linux`               | `Linux`                |
+    /// | `android`             | `Android`              |
+    /// | `windows`             | `Windows`              |
+    /// | `windows` (`uwp`)     | `WindowsStore`         |
+    /// | `cygwin`              | `Windows` + `os.subsystem=cygwin` |
+    /// | `macos`               | `Macos`                |
+    /// | `ios`                 | `iOS`                  |
+    /// | `tvos`                | `tvOS`                 |
+    /// | `watchos`             | `watchOS`              |
+    /// | `visionos`            | `visionOS`             |
+    /// | `freebsd`             | `FreeBSD`              |
+    /// | `solaris`, `illumos`  | `SunOS`                |
+    /// | `aix`                 | `AIX`                  |
+    /// | `emscripten`          | `Emscripten`           |
+    /// | `nto`                 | `Neutrino`             |
+    /// | `vxworks`             | `VxWorks`              |
+    /// | `none`                | `baremetal`            |
+    ///
+    /// | Target triple arch                | Conan `arch`           |
+    /// |-----------------------------------|------------------------|
+    /// | `x86_64`                          | `x86_64`               |
+    /// | `i386`, `i586`, `i686`            | `x86`                  |
+    /// | `aarch64`                         | `armv8`                |
+    /// | `arm64e`                          | `armv8.3`              |
+    /// | `arm64ec`                         | `arm64ec`              |
+    /// | `arm64_32`                        | `armv8_32`             |
+    /// | `arm`, `armv7*`, `thumbv7*`       | `armv7` or `armv7hf`   |
+    /// | `armv7s`, `armv7k`                | `armv7s`, `armv7k`     |
+    /// | `armv6`, `thumbv6m`               | `armv6`                |
+    /// | `armv5te`                         | `armv5el` or `armv5hf` |
+    /// | `armv4t`                          | `armv4`                |
+    /// | `wasm32`, `wasm64`                | `wasm`, `wasm64`       |
+    /// | `asmjs`                           | `asm.js`               |
+    /// | `riscv32*`, `riscv64*`            | `riscv32`, `riscv64`   |
+    /// | `powerpc`                         | `ppc32be`              |
+    /// | `powerpc64`, `powerpc64le`        | `ppc64`, `ppc64le`     |
+    /// | `s390x`                           | `s390x`                |
+    /// | `sparc`, `sparc64`                | `sparc`, `sparcv9`     |
+    /// | `mips*`, `mips64*`                | `mips`, `mips64`       |
+    /// | `avr`                             | `avr`                  |
+    ///
+    /// The ARM hard-float variants are selected for the `*hf` target ABIs.
+    pub fn settings_from_target(&mut self) -> &mut ConanInstall {
+        self.target_settings = true;
+        self
+    }
+
     /// Specifies the custom remote name for `conan install`.
     ///
     /// Matches `--remote` Conan executable option.
@@ -374,6 +934,36 @@ impl ConanInstall {
         self
     }
 
+    /// Adds a Conan generator to run in addition to the recipe generators,
+    /// e.g. `CMakeToolchain`, `CMakeDeps` or `PkgConfigDeps`.
+    ///
+    /// Matches `--generator` Conan executable option.
+    /// Can be called multiple times per Conan invocation.
+    pub fn generator(&mut self, generator: &str) -> &mut ConanInstall {
+        self.generators.push(generator.to_owned());
+        self
+    }
+
+    /// Adds a Conan deployer to copy the dependency files with,
+    /// e.g. `full_deploy`, `direct_deploy` or `runtime_deploy`.
+    ///
+    /// Matches `--deployer` Conan executable option.
+    /// Can be called multiple times per Conan invocation.
+    pub fn deployer(&mut self, deployer: &str) -> &mut ConanInstall {
+        self.deployers.push(deployer.to_owned());
+        self
+    }
+
+    /// Sets the folder to deploy the dependency files into.
+    ///
+    /// Matches `--deployer-folder` Conan executable option.
+    ///
+    /// Defaults to the output folder.
+    pub fn deployer_folder(&mut self, deployer_folder: &Path) -> &mut ConanInstall {
+        self.deployer_folder = Some(deployer_folder.to_owned());
+        self
+    }
+
     /// Sets the Conan command verbosity level.
     ///
     /// Matches `-v` Conan executable option.
@@ -382,60 +972,384 @@ impl ConanInstall {
         self
     }
 
-    /// Adds one extra command line argument to the final `conan install` run.
+    /// Sets the Conan lockfile path to use for installing dependencies.
     ///
-    /// Can be called multiple times per Conan invocation.
-    /// This method is a proxy for [`std::process::Command::arg()`].
-    pub fn arg(&mut self, arg: &str) -> &mut ConanInstall {
-        self.extra_args.push(arg.to_owned());
+    /// Matches `--lockfile` Conan executable option.
+    ///
+    /// The build script is re-run automatically when the lockfile changes.
+    pub fn lockfile(&mut self, lockfile: &Path) -> &mut ConanInstall {
+        self.lockfile = Some(lockfile.to_owned());
         self
     }
 
-    /// Runs the `conan install` command and captures its JSON-formatted output.
+    /// Sets the path to write the updated Conan lockfile to.
     ///
-    /// # Panics
+    /// Matches `--lockfile-out` Conan executable option.
     ///
-    /// Panics if the Conan executable cannot be found.
-    #[must_use]
-    pub fn run(&self) -> ConanOutput {
-        let conan = std::env::var_os(CONAN_ENV).unwrap_or_else(|| DEFAULT_CONAN.into());
-        let recipe = self.recipe_path.as_deref().unwrap_or(Path::new("."));
+    /// NOTE: Writing into the same file as set by [`ConanInstall::lockfile()`]
+    ///       makes Cargo re-run the build script every time.
+    pub fn lockfile_out(&mut self, lockfile_out: &Path) -> &mut ConanInstall {
+        self.lockfile_out = Some(lockfile_out.to_owned());
+        self
+    }
 
-        let output_folder = match &self.output_folder {
-            Some(s) => s.clone(),
-            None => std::env::var_os("OUT_DIR")
-                .expect("OUT_DIR environment variable must be set")
-                .into(),
-        };
+    /// Allows the dependencies not found in the lockfile to be resolved.
+    ///
+    /// Matches `--lockfile-partial` Conan executable option.
+    pub fn lockfile_partial(&mut self) -> &mut ConanInstall {
+        self.lockfile_partial = true;
+        self
+    }
 
-        if self.new_profile {
-            Self::run_profile_detect(&conan, self.profile.as_deref());
+    /// Removes the unused entries from the updated lockfile.
+    ///
+    /// Matches `--lockfile-clean` Conan executable option.
+    pub fn lockfile_clean(&mut self) -> &mut ConanInstall {
+        self.lockfile_clean = true;
+        self
+    }
 
-            if self.build_profile != self.profile {
-                Self::run_profile_detect(&conan, self.build_profile.as_deref());
-            };
-        }
+    /// Reuses the previous `conan install` output if its inputs are unchanged.
+    ///
+    /// The `conan install` inputs fingerprint is computed from the Conan
+    /// executable version, the command line arguments, the recipe, profile
+    /// and lockfile contents and the relevant environment variables.
+    /// The fingerprint is stored in the output folder together with
+    /// the JSON-formatted `conan install` output and its stderr output.
+    ///
+    /// When the fingerprint matches, the saved output is reused and
+    /// `conan install` is not run at all.
+    ///
+    /// Set `CONAN2_FORCE_INSTALL=1` environment variable to force
+    /// a fresh `conan install` run, e.g. after removing packages
+    /// from the Conan cache.
+    pub fn cache_output(&mut self) -> &mut ConanInstall {
+        self.cache = true;
+        self
+    }
 
-        let mut command = Command::new(conan);
+    /// Adds one extra command line argument to the final `conan install` run.
+    ///
+    /// Can be called multiple times per Conan invocation.
+    /// This method is a proxy for [`std::process::Command::arg()`].
+    pub fn arg(&mut self, arg: &str) -> &mut ConanInstall {
+        self.extra_args.push(arg.to_owned());
+        self
+    }
+
+    /// Runs the `conan install` command and captures its JSON-formatted output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Conan executable cannot be found or
+    /// the Conan profile auto-detection fails.
+    #[must_use]
+    pub fn run(&self) -> ConanOutput {
+        self.try_run().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Runs the `conan install` command and captures its JSON-formatted output.
+    ///
+    /// This is a non-panicking version of [`ConanInstall::run()`].
+    ///
+    /// NOTE: A `conan install` command returning a non-zero exit status
+    ///       is not considered an error here.
+    ///       Use [`ConanOutput::try_parse()`] to check the command status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Conan executable cannot be found or
+    /// the Conan profile auto-detection fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output folder is not set and
+    /// the `OUT_DIR` environment variable is not defined.
+    pub fn try_run(&self) -> Result<ConanOutput, ConanError> {
+        let conan = Self::conan_executable();
+        let output_folder = self.output_folder_path();
+
+        self.detect_profiles(&conan)?;
+
+        let mut command = Command::new(&conan);
         command
             .arg("install")
-            .arg(recipe)
+            .arg(self.recipe())
             .arg(format!("-v{}", self.verbosity))
             .arg("--format")
             .arg("json")
             .arg("--output-folder")
-            .arg(output_folder);
+            .arg(&output_folder);
+
+        self.add_graph_args(&mut command, &output_folder)?;
+
+        for generator in &self.generators {
+            command.arg("--generator").arg(generator);
+        }
+
+        for deployer in &self.deployers {
+            command.arg("--deployer").arg(deployer);
+        }
+
+        if let Some(deployer_folder) = self.deployer_folder.as_deref() {
+            command.arg("--deployer-folder").arg(deployer_folder);
+        }
+
+        if let Some(lockfile_out) = self.lockfile_out.as_deref() {
+            command.arg("--lockfile-out").arg(lockfile_out);
+        }
+
+        self.extra_args.iter().for_each(|x| {
+            command.arg(x);
+        });
+
+        let cache = if self.cache {
+            let fingerprint = self.fingerprint(&conan, &command, &output_folder);
+            Some((InstallCache::new(&output_folder), fingerprint))
+        } else {
+            None
+        };
+
+        if let Some((cache, fingerprint)) = &cache {
+            let forced = std::env::var_os(FORCE_INSTALL_ENV).is_some_and(|v| !v.is_empty());
+
+            if let Some((stdout, stderr)) = cache.load(fingerprint).filter(|_| !forced) {
+                return Ok(ConanOutput {
+                    output: Output {
+                        status: ExitStatus::default(),
+                        stdout,
+                        stderr,
+                    },
+                    rerun_paths: self.rerun_paths(),
+                    rerun_envs: self.rerun_envs(),
+                });
+            }
+        }
+
+        let output = command
+            .output()
+            .map_err(|source| ConanError::ExecutableNotFound {
+                program: conan,
+                source,
+            })?;
+
+        if let Some((cache, fingerprint)) = &cache {
+            if output.status.success() {
+                cache.store(fingerprint, &output.stdout, &output.stderr)?;
+            }
+        }
+
+        Ok(ConanOutput {
+            output,
+            rerun_paths: self.rerun_paths(),
+            rerun_envs: self.rerun_envs(),
+        })
+    }
+
+    /// Computes the `conan install` inputs fingerprint.
+    fn fingerprint(&self, conan: &OsStr, command: &Command, output_folder: &Path) -> String {
+        let mut fingerprint = Fingerprint::new();
+
+        let version = Command::new(conan)
+            .arg("--version")
+            .output()
+            .map(|output| output.stdout)
+            .unwrap_or_default();
+
+        fingerprint
+            .value(conan)
+            .value(OsStr::new(&*String::from_utf8_lossy(&version)));
+
+        for arg in command.get_args() {
+            fingerprint.value(arg);
+        }
+
+        for env in self.rerun_envs() {
+            fingerprint.env(&env);
+        }
+
+        for path in self.rerun_paths() {
+            fingerprint.file(&path);
+        }
+
+        // The generated profiles are not listed as the build script inputs.
+        for file_name in [
+            HOST_PROFILE_FILE,
+            HOST_INLINE_PROFILE_FILE,
+            BUILD_INLINE_PROFILE_FILE,
+        ] {
+            fingerprint.file(&output_folder.join(file_name));
+        }
+
+        fingerprint.finish()
+    }
+
+    /// Gets the Conan executable name or path.
+    fn conan_executable() -> OsString {
+        std::env::var_os(CONAN_ENV).unwrap_or_else(|| DEFAULT_CONAN.into())
+    }
+
+    /// Gets the Conan recipe path.
+    fn recipe(&self) -> &Path {
+        self.recipe_path.as_deref().unwrap_or(Path::new("."))
+    }
+
+    /// Gets the Conan generators output folder path.
+    fn output_folder_path(&self) -> PathBuf {
+        match &self.output_folder {
+            Some(s) => s.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .expect("OUT_DIR environment variable must be set")
+                .into(),
+        }
+    }
+
+    /// Runs `conan profile detect` for the host and build profiles if requested.
+    fn detect_profiles(&self, conan: &OsStr) -> Result<(), ConanError> {
+        if self.new_profile {
+            let host_profile = ProfileSource::detectable(self.profile.as_ref());
+            Self::run_profile_detect(conan, host_profile)?;
+
+            let build_profile = ProfileSource::detectable(self.build_profile.as_ref());
+            if build_profile != host_profile {
+                Self::run_profile_detect(conan, build_profile)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the Conan input files which should trigger the build script re-run:
+    /// the recipe file, the named profile files and the lockfile.
+    fn rerun_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        let recipe = self.recipe();
+        if recipe.is_dir() {
+            // Do not watch the whole recipe directory.
+            for file_name in ["conanfile.py", "conanfile.txt"] {
+                paths.push(recipe.join(file_name));
+            }
+        } else {
+            paths.push(recipe.to_owned());
+        }
+
+        let profiles = [
+            (&self.profile, CONAN_DEFAULT_PROFILE_ENV),
+            (&self.build_profile, CONAN_DEFAULT_BUILD_PROFILE_ENV),
+        ];
+
+        for (profile, default_profile_env) in profiles {
+            let name = match profile {
+                Some(ProfileSource::Named(name)) => name.clone(),
+                Some(ProfileSource::Inline(_)) => continue,
+                None => std::env::var(default_profile_env)
+                    .unwrap_or_else(|_| DEFAULT_PROFILE.to_owned()),
+            };
+
+            paths.extend(Self::find_profile(&name));
+        }
+
+        paths.extend(self.lockfile.iter().cloned());
+
+        // Non-existent paths make Cargo re-run the build script every time.
+        paths.retain(|path| path.is_file());
+        paths.dedup();
 
+        paths
+    }
+
+    /// Lists the environment variables which should trigger the build script re-run.
+    fn rerun_envs(&self) -> Vec<String> {
+        let mut envs: Vec<String> = [
+            PROFILE_ENV,
+            TARGET_ENV,
+            CONAN_HOME_ENV,
+            CONAN_DEFAULT_PROFILE_ENV,
+            CONAN_DEFAULT_BUILD_PROFILE_ENV,
+        ]
+        .map(str::to_owned)
+        .into();
+
+        if self.toolchain_profile {
+            envs.extend(HostToolchain::env_vars());
+        }
+
+        if self.cache {
+            envs.push(FORCE_INSTALL_ENV.to_owned());
+        }
+
+        envs
+    }
+
+    /// Finds the Conan profile file path by the profile name or path.
+    ///
+    /// Relative paths are looked up in the current directory first
+    /// and then in the Conan home `profiles` directory.
+    fn find_profile(name: &str) -> Option<PathBuf> {
+        let path = Path::new(name);
+        if path.is_file() {
+            return Some(path.to_owned());
+        }
+
+        let path = Self::conan_home()?.join("profiles").join(name);
+        path.is_file().then_some(path)
+    }
+
+    /// Gets the Conan home directory path: `$CONAN_HOME` or `~/.conan2`.
+    fn conan_home() -> Option<PathBuf> {
+        if let Some(conan_home) = std::env::var_os(CONAN_HOME_ENV) {
+            return Some(conan_home.into());
+        }
+
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+        Some(Path::new(&home).join(".conan2"))
+    }
+
+    /// Adds the dependency graph resolution arguments shared by
+    /// `conan install` and `conan lock create` commands:
+    /// remotes, profiles, settings, options, confs and lockfiles.
+    fn add_graph_args(
+        &self,
+        command: &mut Command,
+        output_folder: &Path,
+    ) -> Result<(), ConanError> {
         if let Some(remote) = self.remote.as_deref() {
             command.arg("--remote");
             command.arg(remote);
         }
 
-        if let Some(profile) = self.profile.as_deref() {
+        let host_profile = match &self.profile {
+            Some(profile) => Some(profile.to_arg(output_folder, HOST_INLINE_PROFILE_FILE)?),
+            None => None,
+        };
+
+        let build_profile = match &self.build_profile {
+            Some(profile) => Some(profile.to_arg(output_folder, BUILD_INLINE_PROFILE_FILE)?),
+            None => None,
+        };
+
+        if self.toolchain_profile {
+            let profile_path = output_folder.join(HOST_PROFILE_FILE);
+            let base_profile = match &host_profile {
+                Some(profile) => profile.to_string_lossy(),
+                None => DEFAULT_PROFILE.into(),
+            };
+            let build_type = match self.build_type.as_deref() {
+                Some(build_type) => Some(build_type),
+                None => Self::build_type_from_env(),
+            };
+
+            HostToolchain::from_env()
+                .profile(Some(&base_profile), build_type)
+                .write(&profile_path)?;
+
+            command.arg("--profile:host").arg(profile_path);
+        } else if let Some(profile) = host_profile {
             command.arg("--profile:host").arg(profile);
         }
 
-        if let Some(build_profile) = self.build_profile.as_deref() {
+        if let Some(build_profile) = build_profile {
             command.arg("--profile:build").arg(build_profile);
         }
 
@@ -450,7 +1364,11 @@ impl ConanInstall {
             command.arg(format!("build_type={build_type}"));
         } else {
             // Otherwise, use additional environment variables set by Cargo.
-            Self::add_settings_from_env(&mut command);
+            Self::add_settings_from_env(command);
+        }
+
+        if self.target_settings {
+            Self::add_settings_from_target(command);
         }
 
         for (scope, key, value) in &self.options {
@@ -463,19 +1381,23 @@ impl ConanInstall {
             command.arg(format!("{key}={value}"));
         }
 
-        self.extra_args.iter().for_each(|x| {
-            command.arg(x);
-        });
+        if let Some(lockfile) = self.lockfile.as_deref() {
+            command.arg("--lockfile").arg(lockfile);
+        }
 
-        let output = command
-            .output()
-            .expect("failed to run the Conan executable");
+        if self.lockfile_partial {
+            command.arg("--lockfile-partial");
+        }
 
-        ConanOutput(output)
+        if self.lockfile_clean {
+            command.arg("--lockfile-clean");
+        }
+
+        Ok(())
     }
 
     /// Creates a new profile with `conan profile detect` if required.
-    fn run_profile_detect(conan: &OsStr, profile: Option<&str>) {
+    fn run_profile_detect(conan: &OsStr, profile: Option<&str>) -> Result<(), ConanError> {
         let mut command = Command::new(conan);
         command.arg("profile").arg("detect").arg("--exist-ok");
 
@@ -489,12 +1411,19 @@ impl ConanInstall {
 
         let status = command
             .status()
-            .expect("failed to run the Conan executable");
+            .map_err(|source| ConanError::ExecutableNotFound {
+                program: conan.to_owned(),
+                source,
+            })?;
 
-        #[allow(clippy::manual_assert)]
         if !status.success() {
-            panic!("'conan profile detect' command failed: {status}");
+            return Err(ConanError::ProfileDetectFailed {
+                profile: profile.map(str::to_owned),
+                status,
+            });
         }
+
+        Ok(())
     }
 
     /// Adds automatic Conan settings arguments derived
@@ -504,16 +1433,57 @@ impl ConanInstall {
     ///
     /// - `build_type`
     fn add_settings_from_env(command: &mut Command) {
-        match std::env::var("PROFILE").as_deref() {
-            Ok("debug") => {
-                command.arg("-s");
-                command.arg("build_type=Debug");
-            }
-            Ok("release") => {
-                command.arg("-s");
-                command.arg("build_type=Release");
+        if let Some(build_type) = Self::build_type_from_env() {
+            command.arg("-s");
+            command.arg(format!("build_type={build_type}"));
+        }
+    }
+
+    /// Infers the Conan build type setting value from the current Cargo build profile.
+    fn build_type_from_env() -> Option<&'static str> {
+        match std::env::var(PROFILE_ENV).as_deref() {
+            Ok("debug") => Some("Debug"),
+            Ok("release") => Some("Release"),
+            _ => None,
+        }
+    }
+
+    /// Adds the Conan host settings arguments inferred
+    /// from the Cargo target information.
+    fn add_settings_from_target(command: &mut Command) {
+        let Some(target) = TargetSettings::from_env() else {
+            return;
+        };
+
+        for (key, value) in target.settings() {
+            command.arg("--settings:host");
+            command.arg(format!("{key}={value}"));
+        }
+    }
+}
+
+impl ProfileSource {
+    /// Gets the profile name for `conan profile detect`:
+    /// `None` stands for the default profile, which is also
+    /// detected for the inline profiles.
+    fn detectable(profile: Option<&ProfileSource>) -> Option<&str> {
+        match profile {
+            Some(ProfileSource::Named(name)) => Some(name),
+            Some(ProfileSource::Inline(_)) | None => None,
+        }
+    }
+
+    /// Gets the `--profile` option argument value writing
+    /// the inline profile into the output folder if necessary.
+    fn to_arg(&self, output_folder: &Path, file_name: &str) -> Result<OsString, ConanError> {
+        match self {
+            ProfileSource::Named(name) => Ok(name.into()),
+            ProfileSource::Inline(profile) => {
+                let path = output_folder.join(file_name);
+                profile.write(&path)?;
+
+                Ok(path.into_os_string())
             }
-            _ => (),
         }
     }
 }
@@ -528,29 +1498,105 @@ impl ConanOutput {
     /// the JSON-formatted Conan output could not be parsed.
     #[must_use]
     pub fn parse(self) -> CargoInstructions {
-        // Panic if the `conan install` command has failed.
-        self.ensure_success();
+        self.try_parse().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Parses `conan install` command output and generates build script
+    /// instructions for Cargo.
+    ///
+    /// This is a non-panicking version of [`ConanOutput::parse()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Conan command invocation failed or
+    /// the JSON-formatted Conan output could not be parsed.
+    pub fn try_parse(self) -> Result<CargoInstructions, ConanError> {
+        self.try_parse_with(&ConanLinkOptions::new())
+    }
+
+    /// Parses `conan install` command output and generates build script
+    /// instructions for Cargo using the custom library linking options.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Conan command invocation failed or
+    /// the JSON-formatted Conan output could not be parsed.
+    #[must_use]
+    pub fn parse_with(self, options: &ConanLinkOptions) -> CargoInstructions {
+        self.try_parse_with(options)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Parses `conan install` command output and generates build script
+    /// instructions for Cargo using the custom library linking options.
+    ///
+    /// This is a non-panicking version of [`ConanOutput::parse_with()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Conan command invocation failed or
+    /// the JSON-formatted Conan output could not be parsed.
+    pub fn try_parse_with(
+        self,
+        options: &ConanLinkOptions,
+    ) -> Result<CargoInstructions, ConanError> {
+        // Bail out if the `conan install` command has failed.
+        self.check_success()?;
 
         let mut cargo = CargoInstructions::new();
 
         // Re-run the build script if `CONAN` environment variable changes.
         cargo.rerun_if_env_changed(CONAN_ENV);
 
+        // Re-run the build script if any of Cargo or Conan settings change.
+        for env in &self.rerun_envs {
+            cargo.rerun_if_env_changed(env);
+        }
+
+        // Re-run the build script if any of the Conan input files change.
+        for path in &self.rerun_paths {
+            cargo.rerun_if_changed(path);
+        }
+
         // Pass Conan warnings through to Cargo using build script instructions.
-        for line in Cursor::new(self.stderr()).lines() {
-            if let Some(msg) = line.unwrap().strip_prefix("WARN: ") {
+        for line in String::from_utf8_lossy(self.stderr()).lines() {
+            if let Some(msg) = line.strip_prefix("WARN: ") {
                 cargo.warning(msg);
             }
         }
 
         // Parse the JSON-formatted `conan install` command output.
-        let metadata: Value =
-            serde_json::from_slice(self.stdout()).expect("failed to parse JSON output");
+        cargo.add_graph_json(self.stdout(), options)?;
 
-        // Walk the dependency graph and collect the C/C++ libraries.
-        ConanDependencyGraph(metadata).traverse(&mut cargo);
+        Ok(cargo)
+    }
 
-        cargo
+    /// Loads the JSON-formatted Conan dependency graph saved earlier
+    /// from `conan install --format json` or `conan graph info --format json`
+    /// command output.
+    ///
+    /// The returned output is treated as a successful `conan install`
+    /// command output with no error messages.
+    /// The build script is re-run automatically when the file changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can not be read.
+    pub fn from_json_file(path: &Path) -> Result<ConanOutput, ConanError> {
+        let stdout = std::fs::read(path).map_err(|source| ConanError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+
+        Ok(ConanOutput {
+            output: Output {
+                status: ExitStatus::default(),
+                stdout,
+                stderr: Vec::new(),
+            },
+            rerun_paths: vec![path.to_owned()],
+            rerun_envs: Vec::new(),
+        })
     }
 
     /// Ensures that the Conan command has been executed successfully.
@@ -559,38 +1605,69 @@ impl ConanOutput {
     ///
     /// Panics with an error message if the Conan command invocation failed.
     pub fn ensure_success(&self) {
+        if let Err(err) = self.check_success() {
+            panic!("{err}");
+        }
+    }
+
+    /// Checks that the Conan command has been executed successfully.
+    fn check_success(&self) -> Result<(), ConanError> {
         if self.is_success() {
-            return;
+            return Ok(());
         }
 
-        let code = self.status_code();
-        let msg = String::from_utf8_lossy(self.stderr());
+        let stderr = String::from_utf8_lossy(self.stderr()).into_owned();
+        // Fall back to the last non-empty line if there are no error messages.
+        let kind = self.error_kind().unwrap_or_else(|| ConanErrorKind::Other {
+            message: stderr
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or_default()
+                .to_owned(),
+        });
+
+        Err(ConanError::InstallFailed {
+            code: self.status_code(),
+            kind,
+            stderr,
+        })
+    }
+
+    /// Classifies the Conan install command failure.
+    ///
+    /// Returns `None` if the Conan command has been executed successfully.
+    #[must_use]
+    pub fn error_kind(&self) -> Option<ConanErrorKind> {
+        if self.is_success() {
+            return None;
+        }
 
-        panic!("Conan failed with status {code}: {msg}");
+        ConanErrorKind::from_stderr(self.stderr())
     }
 
     /// Checks the Conan install command execution status.
     #[must_use]
     pub fn is_success(&self) -> bool {
-        self.0.status.success()
+        self.output.status.success()
     }
 
     /// Gets the Conan install command execution status code.
     #[must_use]
     pub fn status_code(&self) -> i32 {
-        self.0.status.code().unwrap_or_default()
+        self.output.status.code().unwrap_or_default()
     }
 
     /// Gets the Conan JSON-formatted output as bytes.
     #[must_use]
     pub fn stdout(&self) -> &[u8] {
-        &self.0.stdout
+        &self.output.stdout
     }
 
     /// Gets the Conan command error message as bytes.
     #[must_use]
     pub fn stderr(&self) -> &[u8] {
-        &self.0.stderr
+        &self.output.stderr
     }
 }
 
@@ -622,228 +1699,366 @@ impl CargoInstructions {
         self.lib_dirs.iter().cloned().collect()
     }
 
-    /// Creates a new empty Cargo instructions list.
-    fn new() -> CargoInstructions {
-        CargoInstructions {
-            out: Vec::with_capacity(1024),
-            includes: BTreeSet::new(),
-            lib_dirs: BTreeSet::new(),
-        }
+    /// Gets the C/C++ preprocessor definitions (`NAME` or `NAME=VALUE`)
+    /// for all dependencies in the dependency order without duplicates.
+    #[must_use]
+    pub fn defines(&self) -> Vec<String> {
+        self.defines.clone()
     }
 
-    /// Adds `cargo:warning={message}` instruction.
-    fn warning(&mut self, message: &str) {
-        writeln!(self.out, "cargo:warning={message}").unwrap();
+    /// Gets the C compiler flags for all dependencies
+    /// in the dependency order without duplicates.
+    #[must_use]
+    pub fn cflags(&self) -> Vec<String> {
+        self.cflags.clone()
     }
 
-    /// Adds `cargo:rerun-if-env-changed={val}` instruction.
-    fn rerun_if_env_changed(&mut self, val: &str) {
-        writeln!(self.out, "cargo:rerun-if-env-changed={val}").unwrap();
+    /// Gets the C++ compiler flags for all dependencies
+    /// in the dependency order without duplicates.
+    #[must_use]
+    pub fn cxxflags(&self) -> Vec<String> {
+        self.cxxflags.clone()
     }
 
-    /// Adds `cargo:rustc-cdylib-link-arg={val}` instruction.
-    fn rustc_cdylib_link_arg(&mut self, val: &str) {
-        writeln!(self.out, "cargo:rustc-cdylib-link-arg={val}").unwrap();
+    /// Gets the Conan dependency graph resolved by Conan.
+    #[must_use]
+    pub fn graph(&self) -> &ConanGraph {
+        &self.graph
     }
 
-    /// Adds `cargo:rustc-link-arg-bins={val}` instruction.
-    fn rustc_link_arg_bins(&mut self, val: &str) {
-        writeln!(self.out, "cargo:rustc-link-arg-bins={val}").unwrap();
+    /// Lists the files written by the Conan generators into
+    /// the generators folder of the consumer.
+    ///
+    /// Returns an empty list if the Conan dependency graph
+    /// does not report the generators folder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generators folder could not be read.
+    pub fn generated_files(&self) -> Result<ConanGeneratedFiles, ConanError> {
+        let folder = self.graph.root().and_then(ConanNode::generators_folder);
+
+        ConanGeneratedFiles::read(folder)
     }
 
-    /// Adds `cargo:rustc-link-lib=[(dylib|static)=]{lib}` instruction.
+    /// Gets the C/C++ components of the host context package named `package`.
     ///
-    /// The library linking type (dynamic or static) may be inferred
-    /// from the file name pattern on Linux-like platforms.
-    fn rustc_link_lib(&mut self, lib: &str) {
-        // When the full library file name is supplied,
-        // convert `libfoo.a` and `libfoo.so` into `foo` automatically.
-        if let Some(lib) = lib.strip_prefix("lib") {
-            if let Some(lib) = lib.strip_suffix(".a") {
-                self.rustc_link_lib_kind(lib, Some("static"));
-                return;
-            } else if let Some(lib) = lib.strip_suffix(".so") {
-                self.rustc_link_lib_kind(lib, Some("dylib"));
-                return;
-            }
+    /// Returns an empty slice if there is no such package in the graph.
+    #[must_use]
+    pub fn components(&self, package: &str) -> &[CppComponent] {
+        match self.graph.package(package) {
+            Some(node) => node.cpp_info(),
+            None => &[],
         }
+    }
 
-        self.rustc_link_lib_kind(lib, None);
+    /// Gets the C/C++ component named `component` of the host context
+    /// package named `package`.
+    ///
+    /// Use the `root` component name for the packages without components.
+    #[must_use]
+    pub fn component(&self, package: &str, component: &str) -> Option<&CppComponent> {
+        self.graph.package(package)?.component(component)
     }
 
-    /// Adds `cargo:rustc-link-lib=[{kind}=]{lib}` instruction.
-    fn rustc_link_lib_kind(&mut self, lib: &str, kind: Option<&str>) {
-        match kind {
-            Some(kind) => {
-                writeln!(self.out, "cargo:rustc-link-lib={kind}={lib}").unwrap();
-            }
-            None => {
-                writeln!(self.out, "cargo:rustc-link-lib={lib}").unwrap();
-            }
-        }
+    /// Generates build script instructions for Cargo from
+    /// the JSON-formatted Conan dependency graph.
+    ///
+    /// Accepts `conan install --format json` or `conan graph info --format json`
+    /// command output produced earlier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON-formatted Conan dependency graph
+    /// could not be parsed.
+    pub fn from_graph_json(json: &[u8]) -> Result<CargoInstructions, ConanError> {
+        Self::from_graph_json_with(json, &ConanLinkOptions::new())
     }
 
-    /// Adds `cargo:rustc-link-search={path}` instruction.
-    fn rustc_link_search(&mut self, path: &str) {
-        let lib_dir = path.into();
-        if !self.lib_dirs.contains(&lib_dir) {
-            writeln!(self.out, "cargo:rustc-link-search={path}").unwrap();
-            self.lib_dirs.insert(lib_dir);
-        }
+    /// Generates build script instructions for Cargo from
+    /// the JSON-formatted Conan dependency graph using
+    /// the custom library linking options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON-formatted Conan dependency graph
+    /// could not be parsed.
+    pub fn from_graph_json_with(
+        json: &[u8],
+        options: &ConanLinkOptions,
+    ) -> Result<CargoInstructions, ConanError> {
+        let mut cargo = CargoInstructions::new();
+        cargo.add_graph_json(json, options)?;
+
+        Ok(cargo)
     }
 
-    /// Adds `cargo:include={path}` instruction.
-    fn include(&mut self, path: &str) {
-        let include_dir = path.into();
-        if !self.includes.contains(&include_dir) {
-            writeln!(self.out, "cargo:include={path}").unwrap();
-            self.includes.insert(include_dir);
+    /// Creates a new empty Cargo instructions list.
+    fn new() -> CargoInstructions {
+        CargoInstructions {
+            out: Vec::with_capacity(1024),
+            includes: BTreeSet::new(),
+            lib_dirs: BTreeSet::new(),
+            defines: Vec::new(),
+            cflags: Vec::new(),
+            cxxflags: Vec::new(),
+            target_family: TargetFamily::from_env(),
+            graph: ConanGraph::default(),
+            link_options: ConanLinkOptions::default(),
         }
     }
-}
 
-impl ConanDependencyGraph {
-    /// Traverses the dependency graph and emits the `rustc` link instructions
-    /// in the correct linking order.
-    fn traverse(self, cargo: &mut CargoInstructions) {
-        // Consumer package node id: the root of the graph
-        let root_node_id = "0";
+    /// Parses the JSON-formatted Conan dependency graph and
+    /// adds the C/C++ library linking instructions.
+    fn add_graph_json(
+        &mut self,
+        json: &[u8],
+        options: &ConanLinkOptions,
+    ) -> Result<(), ConanError> {
+        let graph = ConanGraph::from_json(json)?;
+
+        // Report the selected package components misspelled by the user.
+        for (package, component) in options.unknown_components(&graph) {
+            self.warning(&format!(
+                "Conan package '{package}' has no component '{component}'"
+            ));
+        }
 
-        self.visit_dependency(cargo, root_node_id);
-    }
+        // Collect the C/C++ libraries in the link order: the dependents first.
+        let mut link_libs = Vec::new();
 
-    /// Visits the dependencies recursively starting from node `node_id`
-    /// and emits `rustc` link instructions.
-    fn visit_dependency(&self, cargo: &mut CargoInstructions, node_id: &str) {
-        let Some(node) = self.find_node(node_id) else {
-            return;
-        };
+        for (node, component) in order::link_order(&graph, options) {
+            // The root node dependency edges carry the effective traits
+            // of all the transitive dependencies.
+            let edge = graph.root().and_then(|root| {
+                root.dependencies()
+                    .iter()
+                    .find(|dependency| dependency.id() == node.id())
+            });
 
-        if let Some(Value::Object(cpp_info)) = node.get("cpp_info") {
-            for cpp_comp_name in cpp_info.keys() {
-                Self::visit_cpp_component(cargo, cpp_info, cpp_comp_name);
-            }
-        };
+            self.add_cpp_component(node, component, options, edge, &mut link_libs);
+        }
 
-        // Recursively visit transitive dependencies.
-        if let Some(Value::Object(dependencies)) = node.get("dependencies") {
-            for dependency_id in dependencies.keys() {
-                self.visit_dependency(cargo, dependency_id);
+        // Emit each library once at its last position in the link order,
+        // so that it follows all the libraries depending on it.
+        for (i, lib) in link_libs.iter().enumerate() {
+            if !link_libs[i + 1..].contains(lib) {
+                writeln!(self.out, "cargo:rustc-link-lib={lib}").unwrap();
             }
-        };
+        }
+
+        self.graph = graph;
+        self.link_options = options.clone();
+
+        Ok(())
     }
 
-    /// Visits the dependency package components recursively starting from
-    /// the component named `comp_name` and emits `rustc` link instructions.
-    fn visit_cpp_component(
-        cargo: &mut CargoInstructions,
-        cpp_info: &Map<String, Value>,
-        comp_name: &str,
+    /// Lists the linked host dependency package components in the link order
+    /// for the runtime library lookup.
+    ///
+    /// Skips the root node, the excluded and the header-only packages,
+    /// as well as the package components not linked at all: the tool
+    /// requirements, the test requirements and the unselected components.
+    fn runtime_components(&self) -> Vec<order::LinkUnit<'_>> {
+        let root_id = self.graph.root().map(ConanNode::id);
+
+        order::link_order(&self.graph, &self.link_options)
+            .into_iter()
+            .filter(|(node, _)| {
+                Some(node.id()) != root_id
+                    && !self.link_options.is_excluded(node)
+                    && !self.link_options.is_headers_only(node)
+            })
+            .collect()
+    }
+
+    /// Emits the package component instructions for `rustc`
+    /// and appends its libraries to the `link_libs` list.
+    ///
+    /// The link instructions are emitted only for the `libs` dependency
+    /// `edge` trait, the include paths and the compile flags are
+    /// collected only for the `headers` trait.
+    // `Option::is_none_or()` requires Rust 1.82.
+    #[allow(clippy::unnecessary_map_or)]
+    fn add_cpp_component(
+        &mut self,
+        node: &ConanNode,
+        component: &CppComponent,
+        options: &ConanLinkOptions,
+        edge: Option<&ConanDependency>,
+        link_libs: &mut Vec<String>,
     ) {
-        let Some(component) = Self::find_cpp_component(cpp_info, comp_name) else {
+        if options.is_excluded(node) {
             return;
-        };
+        }
+
+        if edge.map_or(true, ConanDependency::libs) && !options.is_headers_only(node) {
+            self.add_cpp_component_libs(node, component, options, link_libs);
+        }
+
+        if edge.map_or(true, ConanDependency::headers) {
+            self.add_cpp_component_headers(component);
+        }
+    }
+
+    /// Emits the package component library instructions for `rustc`
+    /// and appends its libraries to the `link_libs` list.
+    fn add_cpp_component_libs(
+        &mut self,
+        node: &ConanNode,
+        component: &CppComponent,
+        options: &ConanLinkOptions,
+        link_libs: &mut Vec<String>,
+    ) {
+        // The explicit link kind override takes precedence over the link kind
+        // inferred from the library file name, then from the package.
+        let link_kind_override = options.link_kind_override(node);
+        let package_link_kind = ConanLinkKind::infer(node, component);
 
         // 1. Emit packaged library link instructions for `rustc`.
-        if let Some(Value::Array(libs)) = component.get("libs") {
-            // FIXME: Many non-library Conan packages in the wild have
-            //        non-empty "libdirs" attribute arrays.
-            //        Ignore them and do not emit bogus library search paths.
-            if !libs.is_empty() {
-                // 1.1. Emit linker search directory instructions for `rustc`.
-                if let Some(Value::Array(libdirs)) = component.get("libdirs") {
-                    for libdir in libdirs {
-                        if let Value::String(libdir) = libdir {
-                            cargo.rustc_link_search(libdir);
-                        }
-                    }
-                }
+        //
+        // FIXME: Many non-library Conan packages in the wild have
+        //        non-empty "libdirs" attribute arrays.
+        //        Ignore them and do not emit bogus library search paths.
+        if !component.libs().is_empty() {
+            // 1.1. Emit linker search directory instructions for `rustc`.
+            for libdir in component.libdirs() {
+                self.rustc_link_search(libdir);
             }
+        }
 
-            // 1.2. Emit library link by name (`-lfoo`) instructions for `rustc`.
-            for lib in libs {
-                if let Value::String(lib) = lib {
-                    cargo.rustc_link_lib(lib);
-                }
-            }
+        // 1.2. Collect library link by name (`-lfoo`) instructions for `rustc`.
+        for lib in component.libs() {
+            let kind = link_kind_override
+                .or(LinkLibrary::new(lib, self.target_family).kind)
+                .or(package_link_kind);
+            let decision = ConanLinkDecision::new(node, component, lib, kind, false);
+            self.push_link_lib(options, decision, link_libs);
         }
 
-        // 2. Emit system library link by name (`-lbar`) instructions for `rustc`.
-        if let Some(Value::Array(system_libs)) = component.get("system_libs") {
-            for system_lib in system_libs {
-                if let Value::String(system_lib) = system_lib {
-                    cargo.rustc_link_lib(system_lib);
-                }
-            }
-        };
+        // 2. Collect system library link by name (`-lbar`) instructions for `rustc`.
+        for system_lib in component.system_libs() {
+            let kind = LinkLibrary::new(system_lib, self.target_family).kind;
+            let decision = ConanLinkDecision::new(node, component, system_lib, kind, true);
+            self.push_link_lib(options, decision, link_libs);
+        }
 
-        // 3. Emit `cargo:include=DIR` metadata for Rust dependencies.
-        if let Some(Value::Array(includedirs)) = component.get("includedirs") {
-            for include in includedirs {
-                if let Value::String(include) = include {
-                    cargo.include(include);
-                }
-            }
-        };
+        // 3. Emit `cargo:rustc-cdylib-link-arg=FLAGS` metadata for `rustc`.
+        for flag in component.sharedlinkflags() {
+            self.rustc_cdylib_link_arg(flag);
+        }
 
-        // 4. Emit `cargo:rustc-cdylib-link-arg=FLAGS` metadata for `rustc`.
-        if let Some(Value::Array(flags)) = component.get("sharedlinkflags") {
-            for flag in flags {
-                if let Value::String(flag) = flag {
-                    cargo.rustc_cdylib_link_arg(flag);
-                }
-            }
+        // 4. Emit `cargo:rustc-link-arg-bins=FLAGS` metadata for `rustc`.
+        for flag in component.exelinkflags() {
+            self.rustc_link_arg_bins(flag);
         }
+    }
 
-        // 5. Emit `cargo:rustc-link-arg-bins=FLAGS` metadata for `rustc`.
-        if let Some(Value::Array(flags)) = component.get("exelinkflags") {
-            for flag in flags {
-                if let Value::String(flag) = flag {
-                    cargo.rustc_link_arg_bins(flag);
-                }
-            }
+    /// Emits the package component include paths
+    /// and collects its C/C++ compile definitions and flags.
+    fn add_cpp_component_headers(&mut self, component: &CppComponent) {
+        // 1. Emit `cargo:include=DIR` metadata for Rust dependencies.
+        for include in component.includedirs() {
+            self.include(include);
         }
 
-        // 6. Recursively visit dependency component requirements.
-        if let Some(Value::Array(requires)) = component.get("requires") {
-            for requirement in requires {
-                if let Value::String(req_comp_name) = requirement {
-                    Self::visit_cpp_component(cargo, cpp_info, req_comp_name);
-                }
-            }
-        };
+        // 2. Collect C/C++ compile definitions and flags.
+        for define in component.defines() {
+            push_unique(&mut self.defines, define);
+        }
+
+        for flag in component.cflags() {
+            push_unique(&mut self.cflags, flag);
+        }
+
+        for flag in component.cxxflags() {
+            push_unique(&mut self.cxxflags, flag);
+        }
     }
 
-    /// Gets the dependency node field map by the node `id` key.
-    fn find_node(&self, id: &str) -> Option<&Map<String, Value>> {
-        let Value::Object(root) = &self.0 else {
-            panic!("root JSON object expected");
-        };
+    /// Adds `cargo:warning={message}` instruction.
+    fn warning(&mut self, message: &str) {
+        writeln!(self.out, "cargo:warning={message}").unwrap();
+    }
 
-        let Some(Value::Object(graph)) = root.get("graph") else {
-            panic!("root 'graph' object expected");
-        };
+    /// Adds `cargo:rerun-if-env-changed={val}` instruction.
+    fn rerun_if_env_changed(&mut self, val: &str) {
+        writeln!(self.out, "cargo:rerun-if-env-changed={val}").unwrap();
+    }
 
-        let Some(Value::Object(nodes)) = graph.get("nodes") else {
-            panic!("root 'nodes' object expected");
-        };
+    /// Adds `cargo:rerun-if-changed={path}` instruction.
+    fn rerun_if_changed(&mut self, path: &Path) {
+        writeln!(self.out, "cargo:rerun-if-changed={}", path.display()).unwrap();
+    }
 
-        if let Some(Value::Object(node)) = nodes.get(id) {
-            Some(node)
-        } else {
-            None
+    /// Adds `cargo:rustc-cdylib-link-arg={val}` instruction.
+    fn rustc_cdylib_link_arg(&mut self, val: &str) {
+        writeln!(self.out, "cargo:rustc-cdylib-link-arg={val}").unwrap();
+    }
+
+    /// Adds `cargo:rustc-link-arg-bins={val}` instruction.
+    fn rustc_link_arg_bins(&mut self, val: &str) {
+        writeln!(self.out, "cargo:rustc-link-arg-bins={val}").unwrap();
+    }
+
+    /// Applies the library link decision callbacks and appends
+    /// the library to the `link_libs` list unless it is skipped.
+    fn push_link_lib(
+        &self,
+        options: &ConanLinkOptions,
+        mut decision: ConanLinkDecision,
+        link_libs: &mut Vec<String>,
+    ) {
+        options.decide(&mut decision);
+
+        if !decision.is_skipped() {
+            link_libs.push(self.link_lib_value(decision.name(), decision.kind()));
         }
     }
 
-    /// Gets the dependency component field map by its name.
-    fn find_cpp_component<'a>(
-        cpp_info: &'a Map<String, Value>,
-        name: &str,
-    ) -> Option<&'a Map<String, Value>> {
-        if let Some(Value::Object(component)) = cpp_info.get(name) {
-            Some(component)
+    /// Formats `[(dylib|static)[:+verbatim]=]{lib}` value
+    /// of the `cargo:rustc-link-lib` instruction.
+    ///
+    /// The library linking type (dynamic or static) is exactly the decided
+    /// `kind`, except for the verbatim file names, which require one:
+    /// the kind inferred from the file name is used for them if none is decided.
+    fn link_lib_value(&self, lib: &str, kind: Option<ConanLinkKind>) -> String {
+        // When the full library file name is supplied,
+        // convert `libfoo.a`, `libfoo.so`, `foo.lib` etc. into `foo` automatically.
+        let lib = LinkLibrary::new(lib, self.target_family);
+        let kind = if lib.verbatim {
+            kind.or(lib.kind)
         } else {
-            None
+            kind
+        };
+
+        match kind {
+            Some(kind) if lib.verbatim => format!("{kind}:+verbatim={}", lib.name),
+            Some(kind) => format!("{kind}={}", lib.name),
+            None => lib.name,
         }
     }
+
+    /// Adds `cargo:rustc-link-search={path}` instruction.
+    fn rustc_link_search(&mut self, path: &Path) {
+        if !self.lib_dirs.contains(path) {
+            writeln!(self.out, "cargo:rustc-link-search={}", path.display()).unwrap();
+            self.lib_dirs.insert(path.to_owned());
+        }
+    }
+
+    /// Adds `cargo:include={path}` instruction.
+    fn include(&mut self, path: &Path) {
+        if !self.includes.contains(path) {
+            writeln!(self.out, "cargo:include={}", path.display()).unwrap();
+            self.includes.insert(path.to_owned());
+        }
+    }
+}
+
+/// Appends the `value` to the list unless it is already present.
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|item| item == value) {
+        list.push(value.to_owned());
+    }
 }
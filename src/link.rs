@@ -0,0 +1,556 @@
+//! C/C++ library linking options
+
+use std::rc::Rc;
+
+use crate::{ConanGraph, ConanNode, ConanPackageType, CppComponent};
+
+/// Cargo target OS environment variable
+const TARGET_OS_ENV: &str = "CARGO_CFG_TARGET_OS";
+
+/// Cargo target environment (C library ABI) environment variable
+const TARGET_ENV_ENV: &str = "CARGO_CFG_TARGET_ENV";
+
+/// Library link decision callback
+type LibraryCallback = Rc<dyn Fn(&mut ConanLinkDecision)>;
+
+/// C/C++ library linking options
+///
+/// Controls the `rustc` link instructions generated from
+/// the Conan dependency graph by [`ConanOutput::parse_with()`](crate::ConanOutput::parse_with)
+/// and [`CargoInstructions::from_graph_json_with()`](crate::CargoInstructions::from_graph_json_with).
+#[derive(Default, Clone)]
+pub struct ConanLinkOptions {
+    /// Library link kind overrides stored as `{package}`, `{kind}`
+    link_kinds: Vec<(String, ConanLinkKind)>,
+    /// Selected package components stored as `{package}`, `{component}`
+    components: Vec<(String, String)>,
+    /// Excluded package names
+    excluded_packages: Vec<String>,
+    /// Header-only package names
+    headers_only_packages: Vec<String>,
+    /// Library link decision callbacks
+    library_callbacks: Vec<LibraryCallback>,
+}
+
+/// Link decision for a single C/C++ library
+///
+/// Passed to the [`ConanLinkOptions::on_library()`] callbacks
+/// before the `cargo:rustc-link-lib` instruction is emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConanLinkDecision {
+    /// Package name
+    package: String,
+    /// Package component name
+    component: String,
+    /// Library name or file name
+    name: String,
+    /// Library link kind
+    kind: Option<ConanLinkKind>,
+    /// System library flag
+    system: bool,
+    /// Skipped library flag
+    skipped: bool,
+}
+
+/// `rustc` library link kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConanLinkKind {
+    /// Static library: `static=`
+    Static,
+    /// Dynamic (shared) library: `dylib=`
+    Dylib,
+}
+
+impl ConanLinkOptions {
+    /// Creates a new default set of linking options.
+    #[must_use]
+    pub fn new() -> ConanLinkOptions {
+        ConanLinkOptions::default()
+    }
+
+    /// Overrides the link kind for all libraries of the package named `package`.
+    ///
+    /// By default the link kind is inferred from the library file name,
+    /// the Conan package type and the `shared` package option.
+    /// The override takes precedence over all of them.
+    /// Can be called multiple times per linking options set.
+    pub fn link_kind(&mut self, package: &str, kind: ConanLinkKind) -> &mut ConanLinkOptions {
+        self.link_kinds.push((package.to_owned(), kind));
+        self
+    }
+
+    /// Links only the `components` of the package named `package`
+    /// and their transitive component requirements.
+    ///
+    /// By default all components of the packages required as a whole
+    /// are linked. The component names missing from the package `cpp_info`
+    /// are reported with `cargo:warning` instructions.
+    /// Can be called multiple times per linking options set.
+    pub fn components(&mut self, package: &str, components: &[&str]) -> &mut ConanLinkOptions {
+        for component in components {
+            self.components
+                .push((package.to_owned(), (*component).to_owned()));
+        }
+        self
+    }
+
+    /// Excludes the package named `package` from the emitted instructions:
+    /// no libraries, link search paths, include paths, flags, rpath entries
+    /// or deployed runtime libraries.
+    ///
+    /// The dependencies of the excluded package are not affected.
+    /// Can be called multiple times per linking options set.
+    pub fn exclude_package(&mut self, package: &str) -> &mut ConanLinkOptions {
+        self.excluded_packages.push(package.to_owned());
+        self
+    }
+
+    /// Emits only the include paths and the compile flags of the package
+    /// named `package`, but not its link instructions, rpath entries
+    /// or deployed runtime libraries.
+    ///
+    /// Useful for linking a system copy of the library instead.
+    /// Can be called multiple times per linking options set.
+    pub fn headers_only(&mut self, package: &str) -> &mut ConanLinkOptions {
+        self.headers_only_packages.push(package.to_owned());
+        self
+    }
+
+    /// Adds a callback to drop, rename or re-kind the individual libraries
+    /// before their `cargo:rustc-link-lib` instructions are emitted.
+    ///
+    /// The callbacks are invoked in the order of addition.
+    /// Can be called multiple times per linking options set.
+    pub fn on_library<F>(&mut self, callback: F) -> &mut ConanLinkOptions
+    where
+        F: Fn(&mut ConanLinkDecision) + 'static,
+    {
+        self.library_callbacks.push(Rc::new(callback));
+        self
+    }
+
+    /// Checks if the package is excluded from the emitted instructions.
+    pub(crate) fn is_excluded(&self, node: &ConanNode) -> bool {
+        node.name()
+            .is_some_and(|name| self.excluded_packages.iter().any(|package| package == name))
+    }
+
+    /// Checks if the package libraries must not be linked.
+    pub(crate) fn is_headers_only(&self, node: &ConanNode) -> bool {
+        node.name().is_some_and(|name| {
+            self.headers_only_packages
+                .iter()
+                .any(|package| package == name)
+        })
+    }
+
+    /// Invokes the library link decision callbacks.
+    pub(crate) fn decide(&self, decision: &mut ConanLinkDecision) {
+        for callback in &self.library_callbacks {
+            callback(decision);
+        }
+    }
+
+    /// Gets the selected components of the package, if any.
+    pub(crate) fn selected_components(&self, node: &ConanNode) -> Option<Vec<&str>> {
+        let name = node.name()?;
+
+        let components: Vec<&str> = self
+            .components
+            .iter()
+            .filter(|(package, _)| package == name)
+            .map(|(_, component)| component.as_str())
+            .collect();
+
+        (!components.is_empty()).then_some(components)
+    }
+
+    /// Lists the selected components missing from the packages
+    /// in the graph as `{package}`, `{component}`.
+    pub(crate) fn unknown_components(&self, graph: &ConanGraph) -> Vec<(&str, &str)> {
+        self.components
+            .iter()
+            .filter(|(package, component)| {
+                graph
+                    .package(package)
+                    .is_some_and(|node| node.component(component).is_none())
+            })
+            .map(|(package, component)| (package.as_str(), component.as_str()))
+            .collect()
+    }
+
+    /// Gets the overridden link kind for the package, if any.
+    pub(crate) fn link_kind_override(&self, node: &ConanNode) -> Option<ConanLinkKind> {
+        let name = node.name()?;
+
+        self.link_kinds
+            .iter()
+            .rev()
+            .find(|(package, _)| package == name)
+            .map(|(_, kind)| *kind)
+    }
+}
+
+impl std::fmt::Debug for ConanLinkOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConanLinkOptions")
+            .field("link_kinds", &self.link_kinds)
+            .field("components", &self.components)
+            .field("excluded_packages", &self.excluded_packages)
+            .field("headers_only_packages", &self.headers_only_packages)
+            .field("library_callbacks", &self.library_callbacks.len())
+            .finish()
+    }
+}
+
+impl ConanLinkDecision {
+    /// Creates a new link decision for the library `name`.
+    pub(crate) fn new(
+        node: &ConanNode,
+        component: &CppComponent,
+        name: &str,
+        kind: Option<ConanLinkKind>,
+        system: bool,
+    ) -> ConanLinkDecision {
+        ConanLinkDecision {
+            package: node.name().unwrap_or_default().to_owned(),
+            component: component.name().to_owned(),
+            name: name.to_owned(),
+            kind,
+            system,
+            skipped: false,
+        }
+    }
+
+    /// Gets the name of the package providing the library.
+    #[must_use]
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    /// Gets the name of the package component providing the library.
+    #[must_use]
+    pub fn component(&self) -> &str {
+        &self.component
+    }
+
+    /// Gets the library name or file name, e.g. `ssl` or `libssl.a`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the library link kind, if known.
+    #[must_use]
+    pub fn kind(&self) -> Option<ConanLinkKind> {
+        self.kind
+    }
+
+    /// Checks if the library is a `system_libs` entry.
+    #[must_use]
+    pub fn is_system(&self) -> bool {
+        self.system
+    }
+
+    /// Checks if the library is skipped.
+    #[must_use]
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+
+    /// Renames the library, e.g. to link a system copy.
+    pub fn rename(&mut self, name: &str) -> &mut ConanLinkDecision {
+        name.clone_into(&mut self.name);
+        self
+    }
+
+    /// Sets the library link kind.
+    ///
+    /// `None` emits the library name without the link kind, so that
+    /// `rustc` picks the default one, unless the library is linked
+    /// verbatim by its file name.
+    pub fn set_kind(&mut self, kind: Option<ConanLinkKind>) -> &mut ConanLinkDecision {
+        self.kind = kind;
+        self
+    }
+
+    /// Skips the library: no `cargo:rustc-link-lib` instruction is emitted.
+    pub fn skip(&mut self) -> &mut ConanLinkDecision {
+        self.skipped = true;
+        self
+    }
+}
+
+impl ConanLinkKind {
+    /// Infers the link kind of the package component libraries
+    /// from the component type, the package type or the `shared` option.
+    pub(crate) fn infer(node: &ConanNode, component: &CppComponent) -> Option<ConanLinkKind> {
+        component
+            .type_()
+            .and_then(Self::from_package_type)
+            .or_else(|| Self::from_package(node))
+    }
+
+    /// Infers the link kind of the package libraries
+    /// from the package type or the `shared` package option.
+    fn from_package(node: &ConanNode) -> Option<ConanLinkKind> {
+        match node.package_type() {
+            ConanPackageType::Library => match node.options().get("shared")?.as_str() {
+                "True" => Some(ConanLinkKind::Dylib),
+                "False" => Some(ConanLinkKind::Static),
+                _ => None,
+            },
+            package_type => Self::from_package_type(package_type),
+        }
+    }
+
+    /// Gets the link kind matching the package type, if any.
+    fn from_package_type(package_type: ConanPackageType) -> Option<ConanLinkKind> {
+        match package_type {
+            ConanPackageType::StaticLibrary => Some(ConanLinkKind::Static),
+            ConanPackageType::SharedLibrary => Some(ConanLinkKind::Dylib),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ConanLinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConanLinkKind::Static => f.write_str("static"),
+            ConanLinkKind::Dylib => f.write_str("dylib"),
+        }
+    }
+}
+
+/// Target platform library file naming conventions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TargetFamily {
+    /// Linux, BSD and other ELF-based platforms: `libfoo.a`, `libfoo.so`
+    Unix,
+    /// macOS and iOS: `libfoo.a`, `libfoo.dylib`
+    Apple,
+    /// Windows MSVC: `foo.lib`, `foo.dll`
+    WindowsMsvc,
+    /// Windows MinGW: `libfoo.a`, `libfoo.dll.a`, `foo.dll`
+    WindowsGnu,
+}
+
+/// Library reference normalized for the `cargo:rustc-link-lib` instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LinkLibrary {
+    /// Library name or the verbatim file name
+    pub name: String,
+    /// Link kind inferred from the file name
+    pub kind: Option<ConanLinkKind>,
+    /// `+verbatim` link modifier flag
+    pub verbatim: bool,
+}
+
+impl TargetFamily {
+    /// Gets the target family from the environment variables
+    /// set by Cargo for the build scripts.
+    ///
+    /// Falls back to the host platform if the variables are not set.
+    pub fn from_env() -> TargetFamily {
+        let target_os = std::env::var(TARGET_OS_ENV);
+        let target_os = target_os.as_deref().unwrap_or(std::env::consts::OS);
+
+        let target_env = std::env::var(TARGET_ENV_ENV);
+        let host_env = if cfg!(target_env = "msvc") {
+            "msvc"
+        } else {
+            ""
+        };
+        let target_env = target_env.as_deref().unwrap_or(host_env);
+
+        Self::new(target_os, target_env)
+    }
+
+    /// Gets the target family from the `target_os`
+    /// and `target_env` configuration values.
+    pub fn new(target_os: &str, target_env: &str) -> TargetFamily {
+        match target_os {
+            "windows" if target_env == "msvc" => TargetFamily::WindowsMsvc,
+            "windows" => TargetFamily::WindowsGnu,
+            "macos" | "ios" | "tvos" | "watchos" | "visionos" => TargetFamily::Apple,
+            _ => TargetFamily::Unix,
+        }
+    }
+}
+
+impl LinkLibrary {
+    /// Normalizes the library name or file name, e.g. `libfoo.so.1`,
+    /// following the target family naming conventions.
+    ///
+    /// The file names that can not be reduced to the library names
+    /// found by the linker `-l{name}` lookup are linked verbatim.
+    pub fn new(lib: &str, family: TargetFamily) -> LinkLibrary {
+        use ConanLinkKind::{Dylib, Static};
+
+        let plain = |name: &str, kind| LinkLibrary {
+            name: name.to_owned(),
+            kind,
+            verbatim: false,
+        };
+
+        let verbatim = |kind| LinkLibrary {
+            name: lib.to_owned(),
+            kind: Some(kind),
+            verbatim: true,
+        };
+
+        let with_prefix = |suffix| lib.strip_prefix("lib")?.strip_suffix(suffix);
+
+        match family {
+            TargetFamily::Unix => {
+                if let Some(name) = with_prefix(".a") {
+                    plain(name, Some(Static))
+                } else if let Some(name) = with_prefix(".so") {
+                    plain(name, Some(Dylib))
+                } else if lib.ends_with(".so") || lib.contains(".so.") {
+                    verbatim(Dylib)
+                } else if lib.ends_with(".a") {
+                    verbatim(Static)
+                } else {
+                    plain(lib, None)
+                }
+            }
+            TargetFamily::Apple => {
+                if let Some(name) = with_prefix(".a") {
+                    plain(name, Some(Static))
+                } else if let Some(name) = with_prefix(".dylib").or_else(|| with_prefix(".tbd")) {
+                    plain(name, Some(Dylib))
+                } else if lib.ends_with(".dylib") || lib.ends_with(".tbd") {
+                    verbatim(Dylib)
+                } else if lib.ends_with(".a") {
+                    verbatim(Static)
+                } else {
+                    plain(lib, None)
+                }
+            }
+            TargetFamily::WindowsMsvc => {
+                // The import libraries and the static libraries
+                // share the same `.lib` file extension.
+                if let Some(name) = lib.strip_suffix(".lib") {
+                    plain(name, None)
+                } else if let Some(name) = lib.strip_suffix(".dll") {
+                    plain(name, Some(Dylib))
+                } else if lib.ends_with(".a") {
+                    verbatim(Static)
+                } else {
+                    plain(lib, None)
+                }
+            }
+            TargetFamily::WindowsGnu => {
+                if let Some(name) = lib.strip_suffix(".dll.a") {
+                    plain(name.strip_prefix("lib").unwrap_or(name), Some(Dylib))
+                } else if let Some(name) = lib.strip_suffix(".dll") {
+                    plain(name.strip_prefix("lib").unwrap_or(name), Some(Dylib))
+                } else if let Some(name) = with_prefix(".a") {
+                    plain(name, Some(Static))
+                } else if let Some(name) = lib.strip_suffix(".lib") {
+                    plain(name, None)
+                } else if lib.ends_with(".a") {
+                    verbatim(Static)
+                } else {
+                    plain(lib, None)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConanLinkKind, LinkLibrary, TargetFamily};
+
+    fn normalize(lib: &str, family: TargetFamily) -> (String, Option<ConanLinkKind>, bool) {
+        let lib = LinkLibrary::new(lib, family);
+        (lib.name, lib.kind, lib.verbatim)
+    }
+
+    fn plain(name: &str, kind: Option<ConanLinkKind>) -> (String, Option<ConanLinkKind>, bool) {
+        (name.to_owned(), kind, false)
+    }
+
+    fn verbatim(name: &str, kind: ConanLinkKind) -> (String, Option<ConanLinkKind>, bool) {
+        (name.to_owned(), Some(kind), true)
+    }
+
+    #[test]
+    fn unix_library_names() {
+        use ConanLinkKind::{Dylib, Static};
+        let family = TargetFamily::Unix;
+
+        assert_eq!(normalize("foo", family), plain("foo", None));
+        assert_eq!(normalize("libfoo.a", family), plain("foo", Some(Static)));
+        assert_eq!(normalize("libfoo.so", family), plain("foo", Some(Dylib)));
+        assert_eq!(
+            normalize("libfoo.so.1.2.3", family),
+            verbatim("libfoo.so.1.2.3", Dylib)
+        );
+        assert_eq!(normalize("foo.a", family), verbatim("foo.a", Static));
+        assert_eq!(normalize("foo.so", family), verbatim("foo.so", Dylib));
+        assert_eq!(
+            normalize("boost_system", family),
+            plain("boost_system", None)
+        );
+    }
+
+    #[test]
+    fn apple_library_names() {
+        use ConanLinkKind::{Dylib, Static};
+        let family = TargetFamily::Apple;
+
+        assert_eq!(normalize("libfoo.a", family), plain("foo", Some(Static)));
+        assert_eq!(normalize("libfoo.dylib", family), plain("foo", Some(Dylib)));
+        assert_eq!(
+            normalize("libfoo.1.dylib", family),
+            plain("foo.1", Some(Dylib))
+        );
+        assert_eq!(normalize("foo.dylib", family), verbatim("foo.dylib", Dylib));
+        assert_eq!(normalize("foo.a", family), verbatim("foo.a", Static));
+    }
+
+    #[test]
+    fn windows_msvc_library_names() {
+        use ConanLinkKind::{Dylib, Static};
+        let family = TargetFamily::WindowsMsvc;
+
+        assert_eq!(normalize("foo.lib", family), plain("foo", None));
+        assert_eq!(normalize("libfoo.lib", family), plain("libfoo", None));
+        assert_eq!(normalize("foo.dll", family), plain("foo", Some(Dylib)));
+        assert_eq!(normalize("libfoo.a", family), verbatim("libfoo.a", Static));
+        assert_eq!(normalize("foo", family), plain("foo", None));
+    }
+
+    #[test]
+    fn windows_gnu_library_names() {
+        use ConanLinkKind::{Dylib, Static};
+        let family = TargetFamily::WindowsGnu;
+
+        assert_eq!(normalize("libfoo.dll.a", family), plain("foo", Some(Dylib)));
+        assert_eq!(normalize("foo.dll.a", family), plain("foo", Some(Dylib)));
+        assert_eq!(normalize("libfoo.a", family), plain("foo", Some(Static)));
+        assert_eq!(normalize("foo.lib", family), plain("foo", None));
+        assert_eq!(normalize("libfoo.dll", family), plain("foo", Some(Dylib)));
+        assert_eq!(normalize("foo.a", family), verbatim("foo.a", Static));
+    }
+
+    #[test]
+    fn target_families() {
+        assert_eq!(TargetFamily::new("linux", "gnu"), TargetFamily::Unix);
+        assert_eq!(TargetFamily::new("freebsd", ""), TargetFamily::Unix);
+        assert_eq!(TargetFamily::new("macos", ""), TargetFamily::Apple);
+        assert_eq!(TargetFamily::new("ios", ""), TargetFamily::Apple);
+        assert_eq!(
+            TargetFamily::new("windows", "msvc"),
+            TargetFamily::WindowsMsvc
+        );
+        assert_eq!(
+            TargetFamily::new("windows", "gnu"),
+            TargetFamily::WindowsGnu
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! `conan lock create` command builder
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{ConanError, ConanInstall, ConanOutput};
+
+/// `conan lock create` command builder
+///
+/// This opaque type implements a command line builder for
+/// the `conan lock create` command invocation.
+///
+/// The lockfile is created for the same recipe, remote, profiles,
+/// settings, options, confs and input lockfile options as configured
+/// for the `conan install` command, so that the following `conan install`
+/// runs are pinned to the exact same recipe revisions.
+///
+/// NOTE: The extra `conan install` arguments are not passed through.
+#[derive(Clone)]
+pub struct ConanLock {
+    /// `conan install` command to take the dependency graph arguments from
+    install: ConanInstall,
+    /// Conan lockfile output path
+    lockfile_out: Option<PathBuf>,
+}
+
+impl ConanLock {
+    /// Creates a new `conan lock create` command resolving the same
+    /// dependency graph as the `conan install` command.
+    #[must_use]
+    pub fn new(install: &ConanInstall) -> ConanLock {
+        ConanLock {
+            install: install.clone(),
+            lockfile_out: None,
+        }
+    }
+
+    /// Sets the path to write the created Conan lockfile to.
+    ///
+    /// Matches `--lockfile-out` Conan executable option.
+    ///
+    /// Defaults to the `conan install` command lockfile output path
+    /// or the input lockfile path, if any of them are set.
+    pub fn lockfile_out(&mut self, lockfile_out: &Path) -> &mut ConanLock {
+        self.lockfile_out = Some(lockfile_out.to_owned());
+        self
+    }
+
+    /// Runs the `conan lock create` command.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Conan executable cannot be found or
+    /// the Conan command invocation failed.
+    pub fn run(&self) {
+        if let Err(err) = self.try_run() {
+            panic!("{err}");
+        }
+    }
+
+    /// Runs the `conan lock create` command.
+    ///
+    /// This is a non-panicking version of [`ConanLock::run()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Conan executable cannot be found or
+    /// the Conan command invocation failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output folder is not set and
+    /// the `OUT_DIR` environment variable is not defined.
+    pub fn try_run(&self) -> Result<(), ConanError> {
+        let install = &self.install;
+        let conan = ConanInstall::conan_executable();
+        let output_folder = install.output_folder_path();
+
+        install.detect_profiles(&conan)?;
+
+        let mut command = Command::new(&conan);
+        command
+            .arg("lock")
+            .arg("create")
+            .arg(install.recipe())
+            .arg(format!("-v{}", install.verbosity));
+
+        install.add_graph_args(&mut command, &output_folder)?;
+
+        let lockfile_out = self
+            .lockfile_out
+            .as_deref()
+            .or(install.lockfile_out.as_deref())
+            .or(install.lockfile.as_deref());
+
+        if let Some(lockfile_out) = lockfile_out {
+            command.arg("--lockfile-out").arg(lockfile_out);
+        }
+
+        let output = command
+            .output()
+            .map_err(|source| ConanError::ExecutableNotFound {
+                program: conan,
+                source,
+            })?;
+
+        ConanOutput {
+            output,
+            rerun_paths: Vec::new(),
+            rerun_envs: Vec::new(),
+        }
+        .check_success()
+    }
+}
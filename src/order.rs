@@ -0,0 +1,371 @@
+//! C/C++ package component link ordering
+
+use std::collections::BTreeSet;
+
+use crate::{ConanContext, ConanDependency, ConanGraph, ConanLinkOptions, ConanNode, CppComponent};
+
+/// Package-level `cpp_info` component name
+const ROOT_COMPONENT: &str = "root";
+
+/// Package component to link
+pub(crate) type LinkUnit<'a> = (&'a ConanNode, &'a CppComponent);
+
+/// Depth-first search state for the link order computation
+struct LinkOrder<'a> {
+    /// Conan dependency graph
+    graph: &'a ConanGraph,
+    /// Linking options
+    options: &'a ConanLinkOptions,
+    /// Visited package components stored as `{node_id}`, `{component}`
+    visited: BTreeSet<(&'a str, &'a str)>,
+    /// Visited packages stored as `{node_id}`
+    visited_packages: BTreeSet<&'a str>,
+    /// Package components in the dependency-first order
+    units: Vec<LinkUnit<'a>>,
+}
+
+/// Computes the link order of the package components reachable from
+/// the root node of the graph: the dependents are placed before
+/// their dependencies, each package component is listed exactly once.
+///
+/// The package components require the components listed in their `requires`:
+/// `comp` for the same package and `pkg::comp` for the dependency packages.
+/// The package-level `root` component also requires all the other components
+/// of the same package. The direct package dependencies are required only
+/// by the `root` component of the packages without other components
+/// and without the explicit `requires`, so that only the required
+/// components of the multi-component dependency packages are linked.
+///
+/// The packages required as a whole are restricted to the components
+/// selected by [`ConanLinkOptions::components()`], if any.
+///
+/// The tool requirements, the build context packages and
+/// the test requirements are skipped.
+pub(crate) fn link_order<'a>(
+    graph: &'a ConanGraph,
+    options: &'a ConanLinkOptions,
+) -> Vec<LinkUnit<'a>> {
+    let mut order = LinkOrder {
+        graph,
+        options,
+        visited: BTreeSet::new(),
+        visited_packages: BTreeSet::new(),
+        units: Vec::new(),
+    };
+
+    if let Some(root) = graph.root() {
+        order.visit_package(root);
+    }
+
+    // Reverse the post-order to put the dependents first.
+    order.units.reverse();
+    order.units
+}
+
+impl<'a> LinkOrder<'a> {
+    /// Visits all the package components and the package dependencies.
+    fn visit_package(&mut self, node: &'a ConanNode) {
+        if !self.visited_packages.insert(node.id()) {
+            return;
+        }
+
+        if let Some(selected) = self.options.selected_components(node) {
+            for comp_name in selected.into_iter().rev() {
+                if let Some(component) = node.component(comp_name) {
+                    self.visit_component(node, component);
+                }
+            }
+            return;
+        }
+
+        if let Some(root) = node.component(ROOT_COMPONENT) {
+            self.visit_component(node, root);
+            return;
+        }
+
+        // The children are visited in the reverse order to list them
+        // in the original order after reversing the post-order.
+        if node.cpp_info().is_empty() {
+            for dependency in self.direct_dependencies(node).into_iter().rev() {
+                self.visit_package(dependency);
+            }
+        }
+
+        for component in node.cpp_info().iter().rev() {
+            self.visit_component(node, component);
+        }
+    }
+
+    /// Visits the package component and its requirements.
+    fn visit_component(&mut self, node: &'a ConanNode, component: &'a CppComponent) {
+        if !self.visited.insert((node.id(), component.name())) {
+            return;
+        }
+
+        for requirement in component.requires().iter().rev() {
+            self.visit_requirement(node, requirement);
+        }
+
+        if component.name() == ROOT_COMPONENT {
+            self.visited_packages.insert(node.id());
+
+            // The components of the multi-component packages and
+            // the explicit package-level requirements replace
+            // the implicit direct package dependencies.
+            if node.cpp_info().len() == 1 && component.requires().is_empty() {
+                for dependency in self.direct_dependencies(node).into_iter().rev() {
+                    self.visit_package(dependency);
+                }
+            }
+
+            for other in node.cpp_info().iter().rev() {
+                self.visit_component(node, other);
+            }
+        }
+
+        self.units.push((node, component));
+    }
+
+    /// Visits the component requirement: `comp` or `pkg::comp`.
+    fn visit_requirement(&mut self, node: &'a ConanNode, requirement: &str) {
+        let Some((package, comp_name)) = requirement.split_once("::") else {
+            if let Some(component) = node.component(requirement) {
+                self.visit_component(node, component);
+            }
+            return;
+        };
+
+        let Some(dependency) = self.dependency(node, package) else {
+            return;
+        };
+
+        match dependency.component(comp_name) {
+            Some(component) => self.visit_component(dependency, component),
+            // `pkg::pkg` refers to the whole package without components.
+            None => self.visit_package(dependency),
+        }
+    }
+
+    /// Gets the direct host dependency nodes of the package.
+    fn direct_dependencies(&self, node: &ConanNode) -> Vec<&'a ConanNode> {
+        node.dependencies()
+            .iter()
+            .filter(|dependency| dependency.direct())
+            .filter_map(|dependency| self.host_dependency(dependency))
+            .collect()
+    }
+
+    /// Gets the host dependency node of the package by the dependency name.
+    fn dependency(&self, node: &ConanNode, name: &str) -> Option<&'a ConanNode> {
+        node.dependencies()
+            .iter()
+            .filter_map(|dependency| self.host_dependency(dependency))
+            .find(|dependency| dependency.name() == Some(name))
+    }
+
+    /// Gets the dependency node unless it is a tool requirement,
+    /// a build context package or a test requirement.
+    fn host_dependency(&self, dependency: &ConanDependency) -> Option<&'a ConanNode> {
+        if dependency.build() || dependency.test() {
+            return None;
+        }
+
+        self.graph
+            .node(dependency.id())
+            .filter(|node| node.context() == ConanContext::Host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::link_order;
+    use crate::{ConanGraph, ConanLinkOptions};
+
+    /// Lists the link order as `{package}` or `{package}::{component}`.
+    fn order(json: &str) -> Vec<String> {
+        order_with(json, &ConanLinkOptions::new())
+    }
+
+    /// Lists the link order with the linking `options`.
+    fn order_with(json: &str, options: &ConanLinkOptions) -> Vec<String> {
+        let graph = ConanGraph::from_json(json.as_bytes()).unwrap();
+
+        link_order(&graph, options)
+            .into_iter()
+            .map(|(node, component)| {
+                let name = node.name().unwrap_or("consumer");
+                match component.name() {
+                    "root" => name.to_owned(),
+                    comp => format!("{name}::{comp}"),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diamond_packages() {
+        // app -> left -> base
+        //     -> right -> base
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {
+                "1": {"direct": true}, "2": {"direct": true}, "3": {"direct": false}}},
+            "1": {"name": "left", "cpp_info": {"root": {}}, "dependencies": {"3": {}}},
+            "2": {"name": "right", "cpp_info": {"root": {}}, "dependencies": {"3": {}}},
+            "3": {"name": "base", "cpp_info": {"root": {}}}
+        }}}"#;
+
+        assert_eq!(order(json), ["consumer", "left", "right", "base"]);
+    }
+
+    #[test]
+    fn diamond_packages_in_reverse_map_order() {
+        // app -> z -> a
+        //     -> a
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {"1": {}, "2": {}}},
+            "1": {"name": "a", "cpp_info": {"root": {}}},
+            "2": {"name": "z", "cpp_info": {"root": {}}, "dependencies": {"1": {}}}
+        }}}"#;
+
+        assert_eq!(order(json), ["consumer", "z", "a"]);
+    }
+
+    #[test]
+    fn diamond_components() {
+        // pkg::d -> pkg::b -> pkg::a
+        //        -> pkg::c -> pkg::a
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {"1": {}}},
+            "1": {"name": "pkg", "cpp_info": {
+                "root": {},
+                "a": {},
+                "b": {"requires": ["a"]},
+                "c": {"requires": ["a"]},
+                "d": {"requires": ["b", "c"]}
+            }}
+        }}}"#;
+
+        assert_eq!(
+            order(json),
+            ["consumer", "pkg", "pkg::d", "pkg::b", "pkg::c", "pkg::a"]
+        );
+    }
+
+    #[test]
+    fn cross_package_components() {
+        // ssl -> crypto -> zlib::zlib
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {"1": {}, "2": {}}},
+            "1": {"name": "zlib", "cpp_info": {"root": {}}},
+            "2": {"name": "openssl", "dependencies": {"1": {}}, "cpp_info": {
+                "root": {},
+                "crypto": {"requires": ["zlib::zlib"]},
+                "ssl": {"requires": ["crypto"]}
+            }}
+        }}}"#;
+
+        assert_eq!(
+            order(json),
+            [
+                "consumer",
+                "openssl",
+                "openssl::ssl",
+                "openssl::crypto",
+                "zlib"
+            ]
+        );
+    }
+
+    #[test]
+    fn required_components_only() {
+        // app -> mylib -> boost::filesystem -> boost::system
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {
+                "1": {"direct": true}, "2": {"direct": false}}},
+            "1": {"name": "mylib", "dependencies": {"2": {}}, "cpp_info": {
+                "root": {},
+                "core": {"requires": ["boost::filesystem"]}
+            }},
+            "2": {"name": "boost", "cpp_info": {
+                "root": {},
+                "filesystem": {"requires": ["system"]},
+                "regex": {},
+                "system": {}
+            }}
+        }}}"#;
+
+        assert_eq!(
+            order(json),
+            [
+                "consumer",
+                "mylib",
+                "mylib::core",
+                "boost::filesystem",
+                "boost::system"
+            ]
+        );
+    }
+
+    #[test]
+    fn selected_components() {
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {"1": {}}},
+            "1": {"name": "boost", "cpp_info": {
+                "root": {},
+                "filesystem": {"requires": ["system"]},
+                "regex": {},
+                "system": {},
+                "thread": {}
+            }}
+        }}}"#;
+
+        let mut options = ConanLinkOptions::new();
+        options.components("boost", &["filesystem"]);
+        assert_eq!(
+            order_with(json, &options),
+            ["consumer", "boost::filesystem", "boost::system"]
+        );
+
+        options.components("boost", &["thread"]);
+        assert_eq!(
+            order_with(json, &options),
+            [
+                "consumer",
+                "boost::filesystem",
+                "boost::system",
+                "boost::thread"
+            ]
+        );
+
+        options.components("other", &["regex"]);
+        assert_eq!(order_with(json, &options).len(), 4);
+    }
+
+    #[test]
+    fn skip_build_and_test_requirements() {
+        // The build context `protobuf` must not satisfy `protobuf::libprotobuf`.
+        let json = r#"{"graph": {"nodes": {
+            "0": {"cpp_info": {"root": {}}, "dependencies": {
+                "1": {"build": true}, "2": {}, "3": {"test": true}}},
+            "1": {"name": "protobuf", "context": "build", "cpp_info": {
+                "root": {}, "libprotobuf": {}}},
+            "2": {"name": "grpc", "dependencies": {"1": {"build": true}, "4": {}},
+                  "cpp_info": {"root": {"requires": ["protobuf::libprotobuf"]}}},
+            "3": {"name": "gtest", "cpp_info": {"root": {}}},
+            "4": {"name": "protobuf", "cpp_info": {"root": {}, "libprotobuf": {}}}
+        }}}"#;
+
+        assert_eq!(order(json), ["consumer", "grpc", "protobuf::libprotobuf"]);
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let json = r#"{"graph": {"nodes": {
+            "0": {"dependencies": {"1": {}}},
+            "1": {"name": "a", "cpp_info": {"root": {}}, "dependencies": {"2": {}}},
+            "2": {"name": "b", "cpp_info": {"root": {}}, "dependencies": {"1": {}}}
+        }}}"#;
+
+        assert_eq!(order(json), ["a", "b"]);
+    }
+}
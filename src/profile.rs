@@ -0,0 +1,200 @@
+//! Conan profile builder
+
+use std::path::Path;
+
+use crate::{ConanError, ConanScope};
+
+/// Conan profile builder
+///
+/// This type implements a builder for the Conan profile files
+/// rendered in the Conan INI profile format.
+///
+/// Use [`ConanInstall::host_profile_inline()`](crate::ConanInstall::host_profile_inline)
+/// and [`ConanInstall::build_profile_inline()`](crate::ConanInstall::build_profile_inline)
+/// to pass it to `conan install`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConanProfile {
+    /// Included profile names or paths
+    includes: Vec<String>,
+    /// Conan settings stored as `{key}={value}`
+    settings: Vec<(String, String)>,
+    /// Conan package options stored as `{scope}:{key}={value}`
+    options: Vec<(String, String, String)>,
+    /// Conan tool requirement references
+    tool_requires: Vec<String>,
+    /// Conan conf options stored as `{key}={value}`
+    confs: Vec<(String, String)>,
+    /// Build environment variables stored as `{name}={value}`
+    buildenv: Vec<(String, String)>,
+    /// Run environment variables stored as `{name}={value}`
+    runenv: Vec<(String, String)>,
+}
+
+impl ConanProfile {
+    /// Creates a new empty Conan profile.
+    #[must_use]
+    pub fn new() -> ConanProfile {
+        ConanProfile::default()
+    }
+
+    /// Includes another Conan profile by name or path.
+    ///
+    /// Matches `include({profile})` profile statement.
+    /// Can be called multiple times per profile.
+    pub fn include(&mut self, profile: &str) -> &mut ConanProfile {
+        self.includes.push(profile.to_owned());
+        self
+    }
+
+    /// Adds a Conan setting.
+    ///
+    /// Matches `[settings]` profile section entries.
+    /// Can be called multiple times per profile.
+    pub fn setting(&mut self, key: &str, value: &str) -> &mut ConanProfile {
+        self.settings.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Adds a Conan package option.
+    ///
+    /// Matches `[options]` profile section entries.
+    /// Can be called multiple times per profile.
+    pub fn option(&mut self, scope: ConanScope, key: &str, value: &str) -> &mut ConanProfile {
+        self.options
+            .push((scope.to_string(), key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Adds a Conan configuration option (conf).
+    ///
+    /// Matches `[conf]` profile section entries.
+    /// Can be called multiple times per profile.
+    pub fn conf(&mut self, key: &str, value: &str) -> &mut ConanProfile {
+        self.confs.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Adds a build environment variable definition.
+    ///
+    /// Matches `[buildenv]` profile section entries.
+    /// Can be called multiple times per profile.
+    pub fn buildenv(&mut self, name: &str, value: &str) -> &mut ConanProfile {
+        self.buildenv.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Adds a run environment variable definition.
+    ///
+    /// Matches `[runenv]` profile section entries.
+    /// Can be called multiple times per profile.
+    pub fn runenv(&mut self, name: &str, value: &str) -> &mut ConanProfile {
+        self.runenv.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Adds a Conan tool requirement reference or pattern, e.g. `cmake/3.30.1`.
+    ///
+    /// Matches `[tool_requires]` profile section entries.
+    /// Can be called multiple times per profile.
+    pub fn tool_require(&mut self, reference: &str) -> &mut ConanProfile {
+        self.tool_requires.push(reference.to_owned());
+        self
+    }
+
+    /// Writes the rendered Conan profile into a file.
+    pub(crate) fn write(&self, path: &Path) -> Result<(), ConanError> {
+        std::fs::write(path, self.to_string()).map_err(|source| ConanError::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    /// Writes a `[{name}]` section with `{key}={value}` entries.
+    fn fmt_section(
+        f: &mut std::fmt::Formatter<'_>,
+        name: &str,
+        entries: &[(String, String)],
+    ) -> std::fmt::Result {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f, "[{name}]")?;
+
+        for (key, value) in entries {
+            writeln!(f, "{key}={value}")?;
+        }
+
+        writeln!(f)
+    }
+}
+
+impl std::fmt::Display for ConanProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for include in &self.includes {
+            writeln!(f, "include({include})")?;
+        }
+
+        if !self.includes.is_empty() {
+            writeln!(f)?;
+        }
+
+        Self::fmt_section(f, "settings", &self.settings)?;
+
+        if !self.options.is_empty() {
+            writeln!(f, "[options]")?;
+
+            for (scope, key, value) in &self.options {
+                writeln!(f, "{scope}:{key}={value}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        if !self.tool_requires.is_empty() {
+            writeln!(f, "[tool_requires]")?;
+
+            for reference in &self.tool_requires {
+                writeln!(f, "{reference}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Self::fmt_section(f, "conf", &self.confs)?;
+        Self::fmt_section(f, "buildenv", &self.buildenv)?;
+        Self::fmt_section(f, "runenv", &self.runenv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConanProfile;
+    use crate::ConanScope;
+
+    #[test]
+    fn render_inline_profile() {
+        let profile = ConanProfile::new()
+            .include("default")
+            .setting("build_type", "Release")
+            .setting("compiler.cppstd", "17")
+            .option(ConanScope::Global, "shared", "False")
+            .option(ConanScope::Package("openssl"), "no_deprecated", "True")
+            .tool_require("cmake/3.30.1")
+            .conf("tools.build:skip_test", "True")
+            .buildenv("CC", "gcc")
+            .runenv("FOO", "bar")
+            .to_string();
+
+        assert_eq!(
+            profile,
+            "include(default)\n\n\
+             [settings]\nbuild_type=Release\ncompiler.cppstd=17\n\n\
+             [options]\n*:shared=False\nopenssl/*:no_deprecated=True\n\n\
+             [tool_requires]\ncmake/3.30.1\n\n\
+             [conf]\ntools.build:skip_test=True\n\n\
+             [buildenv]\nCC=gcc\n\n\
+             [runenv]\nFOO=bar\n\n"
+        );
+    }
+}
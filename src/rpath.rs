@@ -0,0 +1,87 @@
+//! Runtime library search path (rpath) link arguments
+
+use std::io::Write;
+use std::path::Path;
+
+use crate::{push_unique, CargoInstructions, ConanLinkKind, ConanNode, CppComponent, TargetFamily};
+
+/// Runtime library search path (rpath) policy for shared Conan packages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConanRpath {
+    /// Absolute library directory paths in the Conan cache
+    Absolute,
+    /// Executable directory relative path: `$ORIGIN` or `@executable_path`
+    ///
+    /// To be combined with
+    /// [`CargoInstructions::deploy_runtime_libs()`](crate::CargoInstructions::deploy_runtime_libs).
+    Origin,
+}
+
+impl CargoInstructions {
+    /// Adds the runtime library search path (rpath) linker arguments
+    /// for the shared library packages.
+    ///
+    /// Emits `cargo:rustc-link-arg=-Wl,-rpath,{path}` instructions
+    /// if any of the linked host dependencies are shared library packages,
+    /// as indicated by the package type, the `shared` option or
+    /// the library file names.
+    ///
+    /// Nothing is emitted for the static-only dependency graphs
+    /// and for the Windows targets.
+    pub fn rpath(&mut self, policy: ConanRpath) -> &mut CargoInstructions {
+        if matches!(
+            self.target_family,
+            TargetFamily::WindowsMsvc | TargetFamily::WindowsGnu
+        ) {
+            return self;
+        }
+
+        let shared_components: Vec<&CppComponent> = self
+            .runtime_components()
+            .into_iter()
+            .filter(|(node, _)| is_shared_package(node))
+            .map(|(_, component)| component)
+            .collect();
+
+        if shared_components.is_empty() {
+            return self;
+        }
+
+        let mut paths = Vec::new();
+
+        match policy {
+            ConanRpath::Absolute => {
+                for component in shared_components {
+                    for libdir in component.libdirs() {
+                        push_unique(&mut paths, &libdir.display().to_string());
+                    }
+                }
+            }
+            ConanRpath::Origin if self.target_family == TargetFamily::Apple => {
+                paths.push("@executable_path".to_owned());
+            }
+            ConanRpath::Origin => paths.push("$ORIGIN".to_owned()),
+        }
+
+        for path in paths {
+            writeln!(self.out, "cargo:rustc-link-arg=-Wl,-rpath,{path}").unwrap();
+        }
+
+        self
+    }
+}
+
+/// Checks if the package provides shared libraries.
+fn is_shared_package(node: &ConanNode) -> bool {
+    node.cpp_info().iter().any(|component| {
+        ConanLinkKind::infer(node, component) == Some(ConanLinkKind::Dylib)
+            || component.libs().iter().any(|lib| is_shared_lib_name(lib))
+    })
+}
+
+/// Checks if the library name is a shared library file name.
+fn is_shared_lib_name(lib: &str) -> bool {
+    let ext = Path::new(lib).extension().and_then(|ext| ext.to_str());
+
+    matches!(ext, Some("so" | "dylib")) || lib.contains(".so.")
+}
@@ -0,0 +1,255 @@
+//! Conan settings inference from the Cargo target information
+
+/// Cargo target triple environment variable
+const TARGET_ENV: &str = "TARGET";
+
+/// Cargo target OS environment variable
+const TARGET_OS_ENV: &str = "CARGO_CFG_TARGET_OS";
+
+/// Conan host settings inferred from the Cargo target
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct TargetSettings {
+    /// Conan `os` setting value
+    pub os: Option<&'static str>,
+    /// Conan `os.subsystem` setting value
+    pub subsystem: Option<&'static str>,
+    /// Conan `arch` setting value
+    pub arch: Option<&'static str>,
+}
+
+impl TargetSettings {
+    /// Infers the Conan host settings from the environment variables
+    /// set by Cargo for the build scripts.
+    ///
+    /// Returns `None` if the Cargo target information is not available.
+    pub fn from_env() -> Option<TargetSettings> {
+        let triple = std::env::var(TARGET_ENV).ok()?;
+        let target_os = std::env::var(TARGET_OS_ENV).ok()?;
+
+        Some(Self::new(&triple, &target_os))
+    }
+
+    /// Infers the Conan host settings from the Cargo target triple
+    /// and the `target_os` configuration value.
+    pub fn new(triple: &str, target_os: &str) -> TargetSettings {
+        let mut parts = triple.split('-');
+        let arch = parts.next().unwrap_or_default();
+        let vendor = parts.next().unwrap_or_default();
+        let abi = triple.rsplit('-').next().unwrap_or_default();
+
+        let (os, subsystem) = match conan_os(target_os) {
+            // Universal Windows Platform targets use the "uwp" vendor name.
+            Some(("Windows", None)) if vendor == "uwp" => (Some("WindowsStore"), None),
+            Some((os, subsystem)) => (Some(os), subsystem),
+            None => (None, None),
+        };
+
+        TargetSettings {
+            os,
+            subsystem,
+            arch: conan_arch(arch, abi.ends_with("hf")),
+        }
+    }
+
+    /// Lists the inferred Conan settings as `(key, value)` pairs.
+    pub fn settings(&self) -> Vec<(&'static str, &'static str)> {
+        let mut settings = Vec::with_capacity(3);
+
+        if let Some(os) = self.os {
+            settings.push(("os", os));
+        }
+
+        if let Some(subsystem) = self.subsystem {
+            settings.push(("os.subsystem", subsystem));
+        }
+
+        if let Some(arch) = self.arch {
+            settings.push(("arch", arch));
+        }
+
+        settings
+    }
+}
+
+/// Maps the Cargo `target_os` value to the Conan `os` and `os.subsystem` settings.
+fn conan_os(target_os: &str) -> Option<(&'static str, Option<&'static str>)> {
+    let os = match target_os {
+        "linux" => "Linux",
+        "android" => "Android",
+        "windows" => "Windows",
+        "cygwin" => return Some(("Windows", Some("cygwin"))),
+        "macos" => "Macos",
+        "ios" => "iOS",
+        "tvos" => "tvOS",
+        "watchos" => "watchOS",
+        "visionos" => "visionOS",
+        "freebsd" => "FreeBSD",
+        "solaris" | "illumos" => "SunOS",
+        "aix" => "AIX",
+        "emscripten" => "Emscripten",
+        "nto" => "Neutrino",
+        "vxworks" => "VxWorks",
+        "none" => "baremetal",
+        _ => return None,
+    };
+
+    Some((os, None))
+}
+
+/// Maps the Cargo target triple architecture to the Conan `arch` setting.
+///
+/// The `hard_float` flag selects the ARM hard-float ABI variant if exists.
+fn conan_arch(arch: &str, hard_float: bool) -> Option<&'static str> {
+    let arch = match arch {
+        "x86_64" | "x86_64h" => "x86_64",
+        "i386" | "i586" | "i686" => "x86",
+        "aarch64" => "armv8",
+        "arm64e" => "armv8.3",
+        "arm64ec" => "arm64ec",
+        "arm64_32" => "armv8_32",
+        "armv7s" => "armv7s",
+        "armv7k" => "armv7k",
+        "armv4t" => "armv4",
+        "armv5te" if hard_float => "armv5hf",
+        "armv5te" => "armv5el",
+        "armv6" | "thumbv6m" => "armv6",
+        arm if is_armv7(arm) && hard_float => "armv7hf",
+        arm if is_armv7(arm) => "armv7",
+        "wasm32" => "wasm",
+        "wasm64" => "wasm64",
+        "asmjs" => "asm.js",
+        "powerpc" => "ppc32be",
+        "powerpc64" => "ppc64",
+        "powerpc64le" => "ppc64le",
+        "s390x" => "s390x",
+        "sparc" => "sparc",
+        "sparc64" | "sparcv9" => "sparcv9",
+        "mips" | "mipsel" => "mips",
+        "mips64" | "mips64el" => "mips64",
+        "avr" => "avr",
+        riscv if riscv.starts_with("riscv64") => "riscv64",
+        riscv if riscv.starts_with("riscv32") => "riscv32",
+        _ => return None,
+    };
+
+    Some(arch)
+}
+
+/// Checks if the target triple architecture is 32-bit ARMv7 or the generic "arm".
+fn is_armv7(arch: &str) -> bool {
+    arch == "arm" || arch.starts_with("armv7") || arch.starts_with("thumbv7")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TargetSettings;
+
+    fn settings(triple: &str, target_os: &str) -> Vec<(&'static str, &'static str)> {
+        TargetSettings::new(triple, target_os).settings()
+    }
+
+    #[test]
+    fn linux_targets() {
+        assert_eq!(
+            settings("x86_64-unknown-linux-gnu", "linux"),
+            [("os", "Linux"), ("arch", "x86_64")]
+        );
+        assert_eq!(
+            settings("aarch64-unknown-linux-musl", "linux"),
+            [("os", "Linux"), ("arch", "armv8")]
+        );
+        assert_eq!(
+            settings("i686-unknown-linux-gnu", "linux"),
+            [("os", "Linux"), ("arch", "x86")]
+        );
+        assert_eq!(
+            settings("riscv64gc-unknown-linux-gnu", "linux"),
+            [("os", "Linux"), ("arch", "riscv64")]
+        );
+        assert_eq!(
+            settings("powerpc64le-unknown-linux-gnu", "linux"),
+            [("os", "Linux"), ("arch", "ppc64le")]
+        );
+    }
+
+    #[test]
+    fn arm_float_abi() {
+        assert_eq!(
+            settings("armv7-unknown-linux-gnueabihf", "linux"),
+            [("os", "Linux"), ("arch", "armv7hf")]
+        );
+        assert_eq!(
+            settings("armv7-unknown-linux-gnueabi", "linux"),
+            [("os", "Linux"), ("arch", "armv7")]
+        );
+        assert_eq!(
+            settings("arm-unknown-linux-musleabihf", "linux"),
+            [("os", "Linux"), ("arch", "armv7hf")]
+        );
+        assert_eq!(
+            settings("armv7-linux-androideabi", "android"),
+            [("os", "Android"), ("arch", "armv7")]
+        );
+        assert_eq!(
+            settings("thumbv7em-none-eabihf", "none"),
+            [("os", "baremetal"), ("arch", "armv7hf")]
+        );
+    }
+
+    #[test]
+    fn apple_targets() {
+        assert_eq!(
+            settings("aarch64-apple-darwin", "macos"),
+            [("os", "Macos"), ("arch", "armv8")]
+        );
+        assert_eq!(
+            settings("aarch64-apple-ios", "ios"),
+            [("os", "iOS"), ("arch", "armv8")]
+        );
+        assert_eq!(
+            settings("arm64_32-apple-watchos", "watchos"),
+            [("os", "watchOS"), ("arch", "armv8_32")]
+        );
+    }
+
+    #[test]
+    fn windows_targets() {
+        assert_eq!(
+            settings("x86_64-pc-windows-msvc", "windows"),
+            [("os", "Windows"), ("arch", "x86_64")]
+        );
+        assert_eq!(
+            settings("i686-pc-windows-gnu", "windows"),
+            [("os", "Windows"), ("arch", "x86")]
+        );
+        assert_eq!(
+            settings("aarch64-uwp-windows-msvc", "windows"),
+            [("os", "WindowsStore"), ("arch", "armv8")]
+        );
+        assert_eq!(
+            settings("x86_64-pc-cygwin", "cygwin"),
+            [
+                ("os", "Windows"),
+                ("os.subsystem", "cygwin"),
+                ("arch", "x86_64")
+            ]
+        );
+    }
+
+    #[test]
+    fn wasm_targets() {
+        assert_eq!(
+            settings("wasm32-unknown-emscripten", "emscripten"),
+            [("os", "Emscripten"), ("arch", "wasm")]
+        );
+        assert_eq!(
+            settings("wasm32-unknown-unknown", "unknown"),
+            [("arch", "wasm")]
+        );
+    }
+
+    #[test]
+    fn unknown_targets() {
+        assert_eq!(settings("xtensa-esp32-espidf", "espidf"), []);
+    }
+}
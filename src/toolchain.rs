@@ -0,0 +1,407 @@
+//! Conan host profile generation from the C/C++ toolchain used by Cargo
+
+use std::process::Command;
+
+use crate::target::TargetSettings;
+use crate::ConanProfile;
+
+/// Cargo target triple environment variable
+const TARGET_ENV: &str = "TARGET";
+
+/// Cargo target vendor environment variable
+const TARGET_VENDOR_ENV: &str = "CARGO_CFG_TARGET_VENDOR";
+
+/// Cargo target environment (ABI) environment variable
+const TARGET_ENV_ENV: &str = "CARGO_CFG_TARGET_ENV";
+
+/// Cargo target OS environment variable
+const TARGET_OS_ENV: &str = "CARGO_CFG_TARGET_OS";
+
+/// C/C++ compiler family
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompilerKind {
+    /// GNU C/C++ compiler
+    Gcc,
+    /// LLVM Clang compiler
+    Clang,
+    /// Apple Xcode Clang compiler
+    AppleClang,
+    /// Microsoft Visual C/C++ compiler
+    Msvc,
+}
+
+/// C/C++ compiler family and version detected from its version banner
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Compiler {
+    /// Compiler family
+    kind: CompilerKind,
+    /// Compiler major version number
+    major: u32,
+    /// Compiler minor version number
+    minor: u32,
+}
+
+/// Host C/C++ toolchain configured for Cargo and `cc-rs`
+#[derive(Debug, Default, Clone)]
+pub(crate) struct HostToolchain {
+    /// C compiler command
+    cc: String,
+    /// C++ compiler command
+    cxx: String,
+    /// Static library archiver command, if configured explicitly
+    ar: Option<String>,
+    /// Detected C++ compiler
+    compiler: Option<Compiler>,
+    /// Cargo target OS name
+    target_os: String,
+    /// Conan host settings inferred from the Cargo target
+    target: Option<TargetSettings>,
+}
+
+impl HostToolchain {
+    /// Finds the host C/C++ toolchain the same way `cc-rs` does:
+    /// using `CC`, `CXX` and `AR` environment variables and their
+    /// target-specific variants, and detects the C++ compiler version.
+    pub fn from_env() -> HostToolchain {
+        let target = std::env::var(TARGET_ENV).unwrap_or_default();
+        let target_env = std::env::var(TARGET_ENV_ENV).unwrap_or_default();
+        let target_vendor = std::env::var(TARGET_VENDOR_ENV).unwrap_or_default();
+        let target_os = std::env::var(TARGET_OS_ENV).unwrap_or_default();
+
+        let (default_cc, default_cxx) = if target_env == "msvc" {
+            ("cl.exe", "cl.exe")
+        } else if target_vendor == "apple" || target_os == "freebsd" {
+            ("clang", "clang++")
+        } else {
+            ("cc", "c++")
+        };
+
+        let cc = tool_env("CC", &target).unwrap_or_else(|| default_cc.to_owned());
+        let cxx = tool_env("CXX", &target).unwrap_or_else(|| default_cxx.to_owned());
+        let ar = tool_env("AR", &target);
+
+        let compiler = Compiler::detect(&cxx).or_else(|| Compiler::detect(&cc));
+
+        HostToolchain {
+            cc,
+            cxx,
+            ar,
+            compiler,
+            target_os,
+            target: TargetSettings::from_env(),
+        }
+    }
+
+    /// Lists all the environment variables affecting the host toolchain detection.
+    pub fn env_vars() -> Vec<String> {
+        let target = std::env::var(TARGET_ENV).unwrap_or_default();
+
+        ["CC", "CXX", "AR"]
+            .iter()
+            .flat_map(|name| tool_env_vars(name, &target))
+            .collect()
+    }
+
+    /// Creates the Conan host profile describing this toolchain.
+    ///
+    /// The base profile is included first, if specified.
+    pub fn profile(&self, base: Option<&str>, build_type: Option<&str>) -> ConanProfile {
+        let mut profile = ConanProfile::new();
+
+        if let Some(base) = base {
+            profile.include(base);
+        }
+
+        for (key, value) in self.target.iter().flat_map(TargetSettings::settings) {
+            profile.setting(key, value);
+        }
+
+        if let Some(build_type) = build_type {
+            profile.setting("build_type", build_type);
+        }
+
+        if let Some(compiler) = &self.compiler {
+            for (key, value) in compiler.settings(&self.target_os) {
+                profile.setting(key, &value);
+            }
+        }
+
+        profile.buildenv("CC", &self.cc).buildenv("CXX", &self.cxx);
+
+        if let Some(ar) = &self.ar {
+            profile.buildenv("AR", ar);
+        }
+
+        let executables = serde_json::json!({ "c": self.cc, "cpp": self.cxx });
+        profile.conf("tools.build:compiler_executables", &executables.to_string());
+
+        profile
+    }
+}
+
+impl Compiler {
+    /// Detects the compiler family and version by running `{command} --version`.
+    fn detect(command: &str) -> Option<Compiler> {
+        let mut args = command.split_whitespace();
+        let mut program = args.next()?;
+
+        // Skip the compiler cache wrappers.
+        if ["ccache", "sccache", "distcc"]
+            .iter()
+            .any(|w| program.ends_with(w))
+        {
+            program = args.next()?;
+        }
+
+        // MSVC `cl.exe` prints its version banner to `stderr`.
+        let output = Command::new(program)
+            .args(args)
+            .arg("--version")
+            .output()
+            .ok()?;
+
+        let mut banner = String::from_utf8_lossy(&output.stdout).into_owned();
+        banner.push_str(&String::from_utf8_lossy(&output.stderr));
+
+        Self::from_banner(&banner)
+    }
+
+    /// Parses the compiler family and version from its version banner.
+    fn from_banner(banner: &str) -> Option<Compiler> {
+        for line in banner.lines() {
+            let kind = if line.contains("Apple clang") {
+                CompilerKind::AppleClang
+            } else if line.contains("clang version") {
+                CompilerKind::Clang
+            } else if line.contains("Microsoft") && line.contains("Compiler Version") {
+                CompilerKind::Msvc
+            } else if line.contains("(GCC)") || is_gcc_version_line(line) {
+                CompilerKind::Gcc
+            } else {
+                continue;
+            };
+
+            // GCC puts the version number right after the parenthesized
+            // package name, while others prefix it with a "version" word.
+            let version = match line.to_ascii_lowercase().split_once("version ") {
+                Some((_, version)) => version.split_whitespace().next()?.to_owned(),
+                None => line
+                    .split_once(')')
+                    .map_or(line, |(_, version)| version)
+                    .split_whitespace()
+                    .next()?
+                    .to_owned(),
+            };
+
+            let mut numbers = version.split(['.', '-']).map(str::parse::<u32>);
+            let major = numbers.next()?.ok()?;
+            let minor = numbers.next().and_then(Result::ok).unwrap_or_default();
+
+            return Some(Compiler { kind, major, minor });
+        }
+
+        None
+    }
+
+    /// Lists the Conan `compiler.*` settings for this compiler.
+    fn settings(&self, target_os: &str) -> Vec<(&'static str, String)> {
+        let (name, version, cppstd) = match self.kind {
+            CompilerKind::Gcc => {
+                let cppstd = match self.major {
+                    0..6 => "gnu98",
+                    6..11 => "gnu14",
+                    _ => "gnu17",
+                };
+                ("gcc", self.major.to_string(), cppstd)
+            }
+            CompilerKind::Clang => {
+                let cppstd = match self.major {
+                    0..6 => "gnu98",
+                    6..16 => "gnu14",
+                    _ => "gnu17",
+                };
+                ("clang", self.major.to_string(), cppstd)
+            }
+            CompilerKind::AppleClang => ("apple-clang", self.major.to_string(), "gnu98"),
+            CompilerKind::Msvc => {
+                // MSVC 19.38 is Conan "msvc" compiler version 193.
+                let version = self.major * 10 + self.minor / 10;
+                ("msvc", version.to_string(), "14")
+            }
+        };
+
+        let mut settings = vec![
+            ("compiler", name.to_owned()),
+            ("compiler.version", version),
+            ("compiler.cppstd", cppstd.to_owned()),
+        ];
+
+        match (self.kind, target_os) {
+            (CompilerKind::Msvc, _) => settings.push(("compiler.runtime", "dynamic".to_owned())),
+            (CompilerKind::AppleClang, _) => {
+                settings.push(("compiler.libcxx", "libc++".to_owned()))
+            }
+            (CompilerKind::Clang, "macos" | "ios" | "freebsd") => {
+                settings.push(("compiler.libcxx", "libc++".to_owned()));
+            }
+            (CompilerKind::Clang, "android") => {
+                settings.push(("compiler.libcxx", "c++_shared".to_owned()));
+            }
+            _ => settings.push(("compiler.libcxx", "libstdc++11".to_owned())),
+        }
+
+        settings
+    }
+}
+
+/// Checks if the line is a `{program} ({package}) {version}` GCC version line,
+/// e.g. `x86_64-linux-gnu-gcc (Ubuntu 13.2.0-4ubuntu3) 13.2.0`.
+fn is_gcc_version_line(line: &str) -> bool {
+    line.split_once(" (").is_some_and(|(program, _)| {
+        ["gcc", "g++", "cc", "c++"]
+            .iter()
+            .any(|name| program.ends_with(name))
+    })
+}
+
+/// Gets the toolchain program environment variable value
+/// the same way `cc-rs` does.
+fn tool_env(name: &str, target: &str) -> Option<String> {
+    tool_env_vars(name, target)
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .filter(|val| !val.is_empty())
+}
+
+/// Lists the toolchain program environment variable names
+/// in the order of precedence used by `cc-rs`:
+///
+/// `{NAME}_{target}`, `{NAME}_{target_with_underscores}`,
+/// `TARGET_{NAME}` and then `{NAME}`.
+fn tool_env_vars(name: &str, target: &str) -> [String; 4] {
+    let target_underscores = target.replace('-', "_");
+
+    [
+        format!("{name}_{target}"),
+        format!("{name}_{target_underscores}"),
+        format!("TARGET_{name}"),
+        name.to_owned(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compiler, CompilerKind};
+
+    fn settings(banner: &str, target_os: &str) -> Vec<(&'static str, String)> {
+        Compiler::from_banner(banner)
+            .expect("compiler banner not recognized")
+            .settings(target_os)
+    }
+
+    #[test]
+    fn gcc_banner() {
+        let banner = "gcc (Ubuntu 13.2.0-4ubuntu3) 13.2.0\n\
+                      Copyright (C) 2023 Free Software Foundation, Inc.\n";
+
+        assert_eq!(
+            settings(banner, "linux"),
+            [
+                ("compiler", "gcc".to_owned()),
+                ("compiler.version", "13".to_owned()),
+                ("compiler.cppstd", "gnu17".to_owned()),
+                ("compiler.libcxx", "libstdc++11".to_owned()),
+            ]
+        );
+
+        let banner = "gcc (Ubuntu 5.4.0-6ubuntu1~16.04.12) 5.4.0 20160609\n\
+                      Copyright (C) 2015 Free Software Foundation, Inc.\n";
+
+        assert_eq!(
+            settings(banner, "linux"),
+            [
+                ("compiler", "gcc".to_owned()),
+                ("compiler.version", "5".to_owned()),
+                ("compiler.cppstd", "gnu98".to_owned()),
+                ("compiler.libcxx", "libstdc++11".to_owned()),
+            ]
+        );
+
+        let banner = "ccache wrapper (v4) 1.2\n\
+                      x86_64-linux-gnu-gcc (Ubuntu 13.2.0-4ubuntu3) 13.2.0\n\
+                      Copyright (C) 2023 Free Software Foundation, Inc.\n";
+
+        assert_eq!(
+            Compiler::from_banner(banner),
+            Some(Compiler {
+                kind: CompilerKind::Gcc,
+                major: 13,
+                minor: 2
+            })
+        );
+
+        let banner = "gcc (GCC) 9.2.1 20191120 (Red Hat 9.2.1-2)\n";
+
+        assert_eq!(
+            Compiler::from_banner(banner),
+            Some(Compiler {
+                kind: CompilerKind::Gcc,
+                major: 9,
+                minor: 2
+            })
+        );
+    }
+
+    #[test]
+    fn clang_banner() {
+        let banner = "Ubuntu clang version 14.0.0-1ubuntu1.1\n\
+                      Target: x86_64-pc-linux-gnu\n";
+
+        assert_eq!(
+            settings(banner, "linux"),
+            [
+                ("compiler", "clang".to_owned()),
+                ("compiler.version", "14".to_owned()),
+                ("compiler.cppstd", "gnu14".to_owned()),
+                ("compiler.libcxx", "libstdc++11".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn apple_clang_banner() {
+        let banner = "Apple clang version 15.0.0 (clang-1500.1.0.2.5)\n\
+                      Target: arm64-apple-darwin23.2.0\n";
+
+        assert_eq!(
+            settings(banner, "macos"),
+            [
+                ("compiler", "apple-clang".to_owned()),
+                ("compiler.version", "15".to_owned()),
+                ("compiler.cppstd", "gnu98".to_owned()),
+                ("compiler.libcxx", "libc++".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn msvc_banner() {
+        let banner = "cl : Command line warning D9002 : ignoring unknown option '--version'\n\
+                      Microsoft (R) C/C++ Optimizing Compiler Version 19.38.33130 for x64\n";
+
+        assert_eq!(
+            Compiler::from_banner(banner),
+            Some(Compiler {
+                kind: CompilerKind::Msvc,
+                major: 19,
+                minor: 38
+            })
+        );
+        assert_eq!(settings(banner, "windows")[1].1, "193");
+    }
+
+    #[test]
+    fn unknown_banner() {
+        assert_eq!(Compiler::from_banner("tcc version 0.9.27\n"), None);
+    }
+}
@@ -0,0 +1,609 @@
+//! conan2-rs dependency graph parsing tests
+
+use std::path::Path;
+
+use conan2::{
+    CargoInstructions, ConanBinaryStatus, ConanContext, ConanDeployMethod, ConanError, ConanGraph,
+    ConanLinkDecision, ConanLinkKind, ConanLinkOptions, ConanOutput, ConanPackageType, ConanRpath,
+};
+
+/// Saved `conan install --format json` output
+const GRAPH_JSON: &str = "tests/data/conan_graph.json";
+
+fn instructions(cargo: &CargoInstructions) -> String {
+    String::from_utf8(cargo.as_bytes().to_vec()).expect("Invalid UTF-8")
+}
+
+#[test]
+fn parse_graph_json() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+    let cargo = CargoInstructions::from_graph_json(&json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=z\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=xml2\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=ssl\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=crypto\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=pthread\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-search=/conan/p/zlib/p/lib\n"));
+
+    assert!(cargo
+        .include_paths()
+        .contains(&"/conan/p/libxml2/p/include/libxml2".into()));
+}
+
+#[test]
+fn typed_graph_model() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+    let graph = ConanGraph::from_json(&json).unwrap();
+
+    let ids: Vec<&str> = graph.nodes().iter().map(|node| node.id()).collect();
+    assert_eq!(ids, ["0", "1", "2", "3", "4", "5"]);
+
+    let root = graph.root().unwrap();
+    assert_eq!(root.id(), "0");
+    assert_eq!(root.settings().get("build_type").unwrap(), "Release");
+    assert_eq!(root.dependencies().len(), 5);
+    assert_eq!(
+        root.generators_folder(),
+        Some(Path::new("/work/target/out"))
+    );
+
+    let zlib = graph.node("1").unwrap();
+    assert_eq!(zlib.ref_(), "zlib/1.3.1#b8bc2603263cf7eccbd6e17e66b0ed76");
+    assert_eq!(zlib.name(), Some("zlib"));
+    assert_eq!(zlib.version(), Some("1.3.1"));
+    assert_eq!(zlib.user(), None);
+    assert_eq!(zlib.revision(), Some("b8bc2603263cf7eccbd6e17e66b0ed76"));
+    assert_eq!(zlib.package_type(), ConanPackageType::StaticLibrary);
+    assert_eq!(zlib.context(), ConanContext::Host);
+    assert_eq!(zlib.binary(), Some(ConanBinaryStatus::Cache));
+    assert_eq!(zlib.options().get("shared").unwrap(), "False");
+    assert_eq!(zlib.package_folder(), Some(Path::new("/conan/p/zlib/p")));
+
+    let cmake = graph.node("5").unwrap();
+    assert_eq!(cmake.package_type(), ConanPackageType::Application);
+    assert_eq!(cmake.context(), ConanContext::Build);
+
+    let libiconv = root.dependencies().iter().find(|d| d.id() == "3").unwrap();
+    assert!(!libiconv.direct());
+    assert!(libiconv.libs());
+
+    let tool = root.dependencies().iter().find(|d| d.id() == "5").unwrap();
+    assert!(tool.build());
+    assert!(tool.run());
+    assert!(!tool.headers());
+    assert!(!tool.libs());
+    assert!(!tool.visible());
+
+    let cargo = CargoInstructions::from_graph_json(&json).unwrap();
+    assert_eq!(cargo.graph(), &graph);
+}
+
+#[test]
+fn typed_cpp_components() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+    let cargo = CargoInstructions::from_graph_json(&json).unwrap();
+
+    let names: Vec<&str> = cargo
+        .components("openssl")
+        .iter()
+        .map(|component| component.name())
+        .collect();
+    assert_eq!(names, ["crypto", "root", "ssl"]);
+
+    let crypto = cargo.component("openssl", "crypto").unwrap();
+    assert_eq!(crypto.libs(), ["crypto"]);
+    assert_eq!(crypto.system_libs(), ["dl", "pthread", "rt"]);
+    assert_eq!(crypto.requires(), ["zlib::zlib"]);
+    assert_eq!(
+        crypto.properties().get("pkg_config_name").unwrap(),
+        "libcrypto"
+    );
+    assert_eq!(
+        crypto.includedirs(),
+        [Path::new("/conan/p/openssl/p/include")]
+    );
+    assert!(crypto.srcdirs().is_empty());
+    assert_eq!(crypto.type_(), None);
+
+    let libxml2 = cargo.component("libxml2", "root").unwrap();
+    assert_eq!(libxml2.defines(), ["LIBXML_STATIC"]);
+
+    assert!(cargo.components("no-such-package").is_empty());
+    assert!(cargo.component("openssl", "no-such-component").is_none());
+}
+
+#[test]
+fn collect_defines_and_flags() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+    let cargo = CargoInstructions::from_graph_json(&json).unwrap();
+
+    assert_eq!(cargo.defines(), ["LIBXML_STATIC", "OPENSSL_NO_DEPRECATED"]);
+    assert_eq!(cargo.cflags(), ["-pthread"]);
+    assert_eq!(cargo.cxxflags(), ["-pthread"]);
+}
+
+#[cfg(feature = "cc")]
+#[test]
+fn configure_cc_build() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+    let cargo = CargoInstructions::from_graph_json(&json).unwrap();
+
+    let mut build = cc::Build::new();
+    build
+        .target("x86_64-unknown-linux-gnu")
+        .host("x86_64-unknown-linux-gnu")
+        .opt_level(0)
+        .cargo_metadata(false);
+
+    let compiler = cargo.configure_cc(&mut build).get_compiler();
+    let args: Vec<String> = compiler
+        .args()
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+
+    assert!(args
+        .windows(2)
+        .any(|arg| arg == ["-I", "/conan/p/libxml2/p/include/libxml2"]));
+    assert!(args.contains(&"-DLIBXML_STATIC".to_owned()));
+    assert!(args.contains(&"-pthread".to_owned()));
+}
+
+#[test]
+fn list_generated_files() {
+    let folder = Path::new(env!("CARGO_TARGET_TMPDIR")).join("generators");
+    std::fs::create_dir_all(folder.join("subdir")).unwrap();
+
+    for name in [
+        "conan_toolchain.cmake",
+        "conanbuild.sh",
+        "conanrunenv-release-x86_64.sh",
+        "zlib.pc",
+        "ZLIBConfig.cmake",
+    ] {
+        std::fs::write(folder.join(name), "").unwrap();
+    }
+
+    let json = format!(
+        r#"{{"graph": {{"nodes": {{"0": {{"generators_folder": {:?}}}}}}}}}"#,
+        folder.display().to_string()
+    );
+    let cargo = CargoInstructions::from_graph_json(json.as_bytes()).unwrap();
+    let files = cargo.generated_files().unwrap();
+
+    assert_eq!(files.folder(), Some(folder.as_path()));
+    assert_eq!(files.files().len(), 5);
+    assert_eq!(
+        files.cmake_toolchain(),
+        Some(folder.join("conan_toolchain.cmake").as_path())
+    );
+    assert_eq!(files.pkg_config_files(), [folder.join("zlib.pc")]);
+    assert_eq!(
+        files.env_scripts(),
+        [
+            folder.join("conanbuild.sh"),
+            folder.join("conanrunenv-release-x86_64.sh")
+        ]
+    );
+    assert!(files.file("ZLIBConfig.cmake").is_some());
+}
+
+#[test]
+fn deploy_runtime_libs() {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("deploy");
+    let libdir = tmp.join("p/lib");
+    let target = tmp.join("target");
+    std::fs::create_dir_all(&libdir).unwrap();
+    std::fs::create_dir_all(&target).unwrap();
+
+    for name in ["libfoo.so", "libfoo.so.1", "libfoo.a", "foo.pc"] {
+        std::fs::write(libdir.join(name), name).unwrap();
+    }
+
+    let json = format!(
+        r#"{{"graph": {{"nodes": {{
+            "0": {{"dependencies": {{"1": {{}}}}}},
+            "1": {{"context": "host", "cpp_info": {{"root": {{"libdirs": [{:?}]}}}}}}
+        }}}}}}"#,
+        libdir.display().to_string()
+    );
+    let cargo = CargoInstructions::from_graph_json(json.as_bytes()).unwrap();
+
+    let deployed = cargo
+        .deploy_runtime_libs_to(ConanDeployMethod::Copy, &target)
+        .unwrap();
+    assert_eq!(
+        deployed,
+        [target.join("libfoo.so"), target.join("libfoo.so.1")]
+    );
+    assert_eq!(std::fs::read(&deployed[1]).unwrap(), b"libfoo.so.1");
+
+    let deployed = cargo
+        .deploy_runtime_libs_to(ConanDeployMethod::Symlink, &target)
+        .unwrap();
+    assert_eq!(deployed.len(), 2);
+    assert_eq!(std::fs::read(&deployed[0]).unwrap(), b"libfoo.so");
+}
+
+#[test]
+fn rpath_static_packages() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+    let mut cargo = CargoInstructions::from_graph_json(&json).unwrap();
+
+    cargo.rpath(ConanRpath::Absolute).rpath(ConanRpath::Origin);
+
+    assert!(!instructions(&cargo).contains("rpath"));
+}
+
+#[test]
+fn rpath_shared_packages() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}, "2": {}, "3": {}}},
+        "1": {"package_type": "shared-library",
+              "cpp_info": {"root": {"libdirs": ["/conan/p/foo/p/lib"], "libs": ["foo"]}}},
+        "2": {"package_type": "library", "options": {"shared": "True"},
+              "cpp_info": {"root": {"libdirs": ["/conan/p/bar/p/lib"], "libs": ["bar"]}}},
+        "3": {"package_type": "static-library",
+              "cpp_info": {"root": {"libdirs": ["/conan/p/baz/p/lib"], "libs": ["baz"]}}}
+    }}}"#;
+
+    let mut cargo = CargoInstructions::from_graph_json(json).unwrap();
+    cargo.rpath(ConanRpath::Absolute);
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-arg=-Wl,-rpath,/conan/p/foo/p/lib\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-arg=-Wl,-rpath,/conan/p/bar/p/lib\n"));
+    assert!(!emitted_instructions.contains("-rpath,/conan/p/baz/p/lib"));
+
+    let mut cargo = CargoInstructions::from_graph_json(json).unwrap();
+    cargo.rpath(ConanRpath::Origin);
+    let emitted_instructions = instructions(&cargo);
+
+    assert_eq!(emitted_instructions.matches("-rpath").count(), 1);
+    assert!(emitted_instructions.contains("cargo:rustc-link-arg=-Wl,-rpath,$ORIGIN\n"));
+}
+
+#[test]
+fn rpath_and_deploy_filtered_packages() {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("deploy-filtered");
+    let target = tmp.join("target");
+    std::fs::create_dir_all(&target).unwrap();
+
+    for name in ["foo", "bar", "baz", "gtest", "qux_core", "qux_extra"] {
+        let libdir = tmp.join(name).join("lib");
+        std::fs::create_dir_all(&libdir).unwrap();
+        std::fs::write(libdir.join(format!("lib{name}.so")), name).unwrap();
+    }
+
+    let json = format!(
+        r#"{{"graph": {{"nodes": {{
+            "0": {{"dependencies": {{
+                "1": {{}}, "2": {{}}, "3": {{}}, "4": {{"test": true}}, "5": {{}}}}}},
+            "1": {{"name": "foo", "package_type": "shared-library",
+                  "cpp_info": {{"root": {{"libdirs": [{foo:?}], "libs": ["foo"]}}}}}},
+            "2": {{"name": "bar", "package_type": "shared-library",
+                  "cpp_info": {{"root": {{"libdirs": [{bar:?}], "libs": ["bar"]}}}}}},
+            "3": {{"name": "baz", "package_type": "shared-library",
+                  "cpp_info": {{"root": {{"libdirs": [{baz:?}], "libs": ["baz"]}}}}}},
+            "4": {{"name": "gtest", "package_type": "shared-library",
+                  "cpp_info": {{"root": {{"libdirs": [{gtest:?}], "libs": ["gtest"]}}}}}},
+            "5": {{"name": "qux", "package_type": "shared-library", "cpp_info": {{
+                  "root": {{}},
+                  "core": {{"libdirs": [{qux_core:?}], "libs": ["qux_core"]}},
+                  "extra": {{"libdirs": [{qux_extra:?}], "libs": ["qux_extra"]}}}}}}
+        }}}}}}"#,
+        foo = tmp.join("foo/lib").display().to_string(),
+        bar = tmp.join("bar/lib").display().to_string(),
+        baz = tmp.join("baz/lib").display().to_string(),
+        gtest = tmp.join("gtest/lib").display().to_string(),
+        qux_core = tmp.join("qux_core/lib").display().to_string(),
+        qux_extra = tmp.join("qux_extra/lib").display().to_string(),
+    );
+
+    let mut cargo = CargoInstructions::from_graph_json_with(
+        json.as_bytes(),
+        ConanLinkOptions::new()
+            .exclude_package("bar")
+            .headers_only("baz")
+            .components("qux", &["core"]),
+    )
+    .unwrap();
+    cargo.rpath(ConanRpath::Absolute);
+    let emitted_instructions = instructions(&cargo);
+
+    assert_eq!(emitted_instructions.matches("-rpath").count(), 2);
+    assert!(emitted_instructions.contains("foo/lib\n"));
+    assert!(emitted_instructions.contains("qux_core/lib\n"));
+
+    let deployed = cargo
+        .deploy_runtime_libs_to(ConanDeployMethod::Copy, &target)
+        .unwrap();
+    assert_eq!(
+        deployed,
+        [target.join("libfoo.so"), target.join("libqux_core.so")]
+    );
+}
+
+#[test]
+fn infer_link_kind() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}, "2": {}, "3": {}, "4": {}}},
+        "1": {"name": "foo", "package_type": "shared-library",
+              "cpp_info": {"root": {"libs": ["foo"]}}},
+        "2": {"name": "bar", "package_type": "library", "options": {"shared": "False"},
+              "cpp_info": {"root": {"libs": ["bar"], "system_libs": ["m"]}}},
+        "3": {"name": "baz", "package_type": "unknown",
+              "cpp_info": {"root": {"libs": ["baz", "libqux.so"]}}},
+        "4": {"name": "quux", "package_type": "static-library",
+              "cpp_info": {"root": {"libs": ["quux", "libcorge.a"]}}}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=foo\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=bar\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=m\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=baz\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=qux\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=quux\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=corge\n"));
+
+    let cargo = CargoInstructions::from_graph_json_with(
+        json,
+        ConanLinkOptions::new()
+            .link_kind("foo", ConanLinkKind::Static)
+            .link_kind("baz", ConanLinkKind::Dylib)
+            .link_kind("quux", ConanLinkKind::Dylib),
+    )
+    .unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    // The override wins over the `.a` file name.
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=corge\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=quux\n"));
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=foo\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=baz\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=bar\n"));
+}
+
+#[test]
+fn verbatim_library_names() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}}},
+        "1": {"name": "foo", "package_type": "shared-library",
+              "cpp_info": {"root": {"libs": ["libfoo.so.1.2", "libbar.so", "baz.a"]}}}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib:+verbatim=libfoo.so.1.2\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=bar\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static:+verbatim=baz.a\n"));
+}
+
+#[test]
+fn static_link_order() {
+    // app -> left -> base
+    //     -> right -> base::core
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {"direct": false}, "2": {}, "3": {}}},
+        "1": {"name": "base", "package_type": "static-library", "cpp_info": {
+            "root": {},
+            "core": {"libs": ["core"], "system_libs": ["pthread"]},
+            "extra": {"libs": ["extra"], "requires": ["core"]}
+        }},
+        "2": {"name": "left", "package_type": "static-library", "dependencies": {"1": {}},
+              "cpp_info": {"root": {"libs": ["left"], "system_libs": ["pthread"]}}},
+        "3": {"name": "right", "package_type": "static-library", "dependencies": {"1": {}},
+              "cpp_info": {"root": {"libs": ["right"], "requires": ["base::core"]}}}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    let libs: Vec<&str> = emitted_instructions
+        .lines()
+        .filter_map(|line| line.strip_prefix("cargo:rustc-link-lib="))
+        .collect();
+
+    assert_eq!(
+        libs,
+        [
+            "static=left",
+            "static=extra",
+            "static=right",
+            "static=core",
+            "pthread"
+        ]
+    );
+}
+
+#[test]
+fn link_required_components() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}, "2": {"direct": false}}},
+        "1": {"name": "client", "dependencies": {"2": {}}, "cpp_info": {
+            "root": {},
+            "client": {"libs": ["client"], "requires": ["openssl::ssl"]}
+        }},
+        "2": {"name": "openssl", "cpp_info": {
+            "root": {},
+            "crypto": {"libs": ["crypto"], "libdirs": ["/conan/p/openssl/p/lib"]},
+            "legacy": {"libs": ["legacy"], "requires": ["crypto"]},
+            "ssl": {"libs": ["ssl"], "requires": ["crypto"]}
+        }}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=client\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=ssl\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=crypto\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-search=/conan/p/openssl/p/lib\n"));
+    assert!(!emitted_instructions.contains("legacy"));
+}
+
+#[test]
+fn honour_dependency_traits() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {
+            "1": {"headers": false},
+            "2": {"libs": false},
+            "3": {"build": true, "headers": false, "libs": false},
+            "4": {"test": true}
+        }},
+        "1": {"name": "impl", "cpp_info": {"root": {
+            "libs": ["impl"], "libdirs": ["/conan/p/impl/p/lib"],
+            "includedirs": ["/conan/p/impl/p/include"], "defines": ["IMPL"]}}},
+        "2": {"name": "header", "cpp_info": {"root": {
+            "libs": ["header"], "libdirs": ["/conan/p/header/p/lib"],
+            "includedirs": ["/conan/p/header/p/include"], "defines": ["HEADER"]}}},
+        "3": {"name": "tool", "context": "build", "cpp_info": {"root": {
+            "libs": ["tool"], "includedirs": ["/conan/p/tool/p/include"]}}},
+        "4": {"name": "gtest", "cpp_info": {"root": {
+            "libs": ["gtest"], "includedirs": ["/conan/p/gtest/p/include"]}}}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=impl\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-search=/conan/p/impl/p/lib\n"));
+    assert!(!emitted_instructions.contains("/conan/p/impl/p/include"));
+
+    assert!(emitted_instructions.contains("cargo:include=/conan/p/header/p/include\n"));
+    assert!(!emitted_instructions.contains("rustc-link-lib=header"));
+    assert!(!emitted_instructions.contains("/conan/p/header/p/lib"));
+
+    assert!(!emitted_instructions.contains("tool"));
+    assert!(!emitted_instructions.contains("gtest"));
+    assert_eq!(cargo.defines(), ["HEADER"]);
+}
+
+#[test]
+fn filter_link_libraries() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}, "2": {}, "3": {}}},
+        "1": {"name": "openssl", "package_type": "static-library", "cpp_info": {"root": {
+            "libs": ["ssl", "crypto"], "libdirs": ["/conan/p/openssl/p/lib"],
+            "system_libs": ["dl"], "includedirs": ["/conan/p/openssl/p/include"]}}},
+        "2": {"name": "zlib", "package_type": "static-library", "cpp_info": {"root": {
+            "libs": ["z"], "includedirs": ["/conan/p/zlib/p/include"]}}},
+        "3": {"name": "bzip2", "package_type": "static-library", "cpp_info": {"root": {
+            "libs": ["bz2"], "system_libs": ["m"], "includedirs": ["/conan/p/bzip2/p/include"]}}}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json_with(
+        json,
+        ConanLinkOptions::new()
+            .headers_only("openssl")
+            .exclude_package("zlib")
+            .on_library(|decision: &mut ConanLinkDecision| {
+                assert_eq!(decision.package(), "bzip2");
+                assert_eq!(decision.component(), "root");
+
+                if decision.is_system() {
+                    decision.skip();
+                } else {
+                    assert_eq!(decision.kind(), Some(ConanLinkKind::Static));
+                    decision
+                        .rename("bzip2")
+                        .set_kind(Some(ConanLinkKind::Dylib));
+                }
+            }),
+    )
+    .unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:include=/conan/p/openssl/p/include\n"));
+    assert!(!emitted_instructions.contains("/conan/p/openssl/p/lib"));
+    assert!(!emitted_instructions.contains("ssl\n"));
+    assert!(!emitted_instructions.contains("dl\n"));
+
+    assert!(!emitted_instructions.contains("zlib"));
+    assert!(!emitted_instructions.contains("=z\n"));
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib=bzip2\n"));
+    assert!(!emitted_instructions.contains("=bz2\n"));
+    assert!(!emitted_instructions.contains("=m\n"));
+}
+
+#[test]
+fn clear_link_kind() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}}},
+        "1": {"name": "foo", "package_type": "static-library",
+              "cpp_info": {"root": {"libs": ["libfoo.a", "libbar.so.1"]}}}
+    }}}"#;
+
+    let cargo = CargoInstructions::from_graph_json_with(
+        json,
+        ConanLinkOptions::new().on_library(|decision: &mut ConanLinkDecision| {
+            assert!(decision.kind().is_some());
+            decision.set_kind(None);
+        }),
+    )
+    .unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=foo\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=dylib:+verbatim=libbar.so.1\n"));
+}
+
+#[test]
+fn warn_unknown_components() {
+    let json = std::fs::read(GRAPH_JSON).unwrap();
+
+    let cargo = CargoInstructions::from_graph_json_with(
+        &json,
+        ConanLinkOptions::new()
+            .components("openssl", &["ssl", "nope"])
+            .components("no-such-package", &["foo"]),
+    )
+    .unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions
+        .contains("cargo:warning=Conan package 'openssl' has no component 'nope'\n"));
+    assert!(!emitted_instructions.contains("no-such-package"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=ssl\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=crypto\n"));
+}
+
+#[test]
+fn parse_json_file() {
+    let cargo = ConanOutput::from_json_file(Path::new(GRAPH_JSON))
+        .unwrap()
+        .parse();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains(&format!("cargo:rerun-if-changed={GRAPH_JSON}\n")));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=z\n"));
+}
+
+#[test]
+fn fail_no_json_file() {
+    let result = ConanOutput::from_json_file(Path::new("tests/data/no-such-file.json"));
+
+    assert!(matches!(result, Err(ConanError::Io { .. })));
+}
+
+#[test]
+fn fail_invalid_json() {
+    let result = CargoInstructions::from_graph_json(b"{\"graph\": ");
+
+    assert!(matches!(result, Err(ConanError::InvalidJson(_))));
+}
+
+#[test]
+fn fail_unexpected_schema() {
+    let result = CargoInstructions::from_graph_json(b"{\"graph\": {}}");
+
+    assert!(matches!(result, Err(ConanError::UnexpectedSchema(_))));
+}
@@ -2,7 +2,9 @@
 
 use std::{io::Write, path::Path};
 
-use conan2::{ConanInstall, ConanScope, ConanVerbosity};
+use conan2::{
+    ConanError, ConanErrorKind, ConanInstall, ConanLock, ConanProfile, ConanScope, ConanVerbosity,
+};
 
 #[test]
 fn run_conan_install() {
@@ -35,6 +37,11 @@ fn run_conan_install() {
 
     assert!(includes.len() > 3);
 
+    let emitted_instructions = String::from_utf8(cargo.as_bytes().to_vec()).expect("Invalid UTF-8");
+    assert!(emitted_instructions.contains("cargo:rerun-if-changed=tests/conanfile.txt"));
+    assert!(emitted_instructions.contains("cargo:rerun-if-env-changed=CONAN_HOME"));
+    assert!(emitted_instructions.contains("cargo:rerun-if-env-changed=PROFILE"));
+
     cargo.emit();
 }
 
@@ -54,6 +61,28 @@ fn fail_no_conanfile() {
     assert!(output
         .stderr()
         .starts_with(b"ERROR: Conanfile not found at"));
+    assert!(matches!(
+        output.error_kind(),
+        Some(ConanErrorKind::MissingConanfile { .. })
+    ));
+}
+
+#[test]
+fn try_fail_no_conanfile() {
+    let result = ConanInstall::new()
+        .output_folder(Path::new(env!("CARGO_TARGET_TMPDIR")))
+        .build_type("Debug")
+        .try_run()
+        .expect("failed to run the Conan executable")
+        .try_parse();
+
+    let Err(ConanError::InstallFailed { code, kind, stderr }) = result else {
+        panic!("'conan install' failure expected");
+    };
+
+    assert_eq!(code, 1);
+    assert!(matches!(kind, ConanErrorKind::MissingConanfile { .. }));
+    assert!(stderr.starts_with("ERROR: Conanfile not found at"));
 }
 
 #[test]
@@ -70,6 +99,12 @@ fn fail_no_remote() {
     assert_eq!(output.status_code(), 1);
     assert_eq!(output.stdout().len(), 0);
     assert!(output.stderr().starts_with(b"ERROR: Remote '"));
+    assert_eq!(
+        output.error_kind(),
+        Some(ConanErrorKind::UnknownRemote {
+            remote: "no-such-remote".to_owned()
+        })
+    );
 }
 
 #[test]
@@ -86,6 +121,12 @@ fn fail_no_profile() {
     assert_eq!(output.status_code(), 1);
     assert_eq!(output.stdout().len(), 0);
     assert!(output.stderr().starts_with(b"ERROR: Profile not found: "));
+    assert_eq!(
+        output.error_kind(),
+        Some(ConanErrorKind::MissingProfile {
+            profile: "no-such-profile".to_owned()
+        })
+    );
 }
 
 #[test]
@@ -105,6 +146,12 @@ fn fail_unknown_args() {
     assert!(str::from_utf8(output.stderr())
         .unwrap()
         .contains("error: unrecognized arguments: --no-such-argument"));
+    assert_eq!(
+        output.error_kind(),
+        Some(ConanErrorKind::UnrecognizedArguments {
+            arguments: "--no-such-argument".to_owned()
+        })
+    );
 }
 
 #[test]
@@ -137,6 +184,105 @@ fn host_and_build_profiles() {
     assert!(output.is_success());
 }
 
+#[test]
+fn host_and_build_inline_profiles() {
+    let host_profile = ConanProfile::new()
+        .include("default")
+        .setting("build_type", "Release")
+        .option(ConanScope::Global, "shared", "True")
+        .clone();
+
+    let build_profile = ConanProfile::new().include("default").clone();
+
+    let output = ConanInstall::with_recipe(Path::new("tests/conanfile.txt"))
+        .output_folder(Path::new(env!("CARGO_TARGET_TMPDIR")))
+        .host_profile_inline(&host_profile)
+        .build_profile_inline(&build_profile)
+        .detect_profile() // Creates the included "default" profile
+        .build("missing")
+        .verbosity(ConanVerbosity::Debug)
+        .run();
+
+    std::io::stderr().write_all(output.stderr()).unwrap();
+    assert!(output.is_success());
+}
+
+#[test]
+fn create_and_use_lockfile() {
+    let lockfile = Path::new(env!("CARGO_TARGET_TMPDIR")).join("conan.lock");
+
+    let mut install = ConanInstall::with_recipe(Path::new("tests/conanfile.txt"));
+    install
+        .output_folder(Path::new(env!("CARGO_TARGET_TMPDIR")))
+        .detect_profile()
+        .build("missing")
+        .verbosity(ConanVerbosity::Debug);
+
+    ConanLock::new(&install).lockfile_out(&lockfile).run();
+    assert!(lockfile.is_file());
+
+    let output = install.lockfile(&lockfile).run();
+
+    std::io::stderr().write_all(output.stderr()).unwrap();
+    assert!(output.is_success());
+
+    let cargo = output.parse();
+    let emitted_instructions = String::from_utf8(cargo.as_bytes().to_vec()).expect("Invalid UTF-8");
+    assert!(
+        emitted_instructions.contains(&format!("cargo:rerun-if-changed={}", lockfile.display()))
+    );
+}
+
+#[test]
+fn reuse_cached_output() {
+    let output_folder = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cached");
+    std::fs::create_dir_all(&output_folder).unwrap();
+
+    let mut install = ConanInstall::with_recipe(Path::new("tests/conanfile.txt"));
+    install
+        .output_folder(&output_folder)
+        .detect_profile()
+        .build("missing")
+        .cache_output();
+
+    let first = install.run();
+    assert!(first.is_success());
+    assert!(output_folder.join("conan_install.json").is_file());
+
+    let second = install.run();
+    assert!(second.is_success());
+    assert_eq!(first.stdout(), second.stdout());
+    assert_eq!(first.stderr(), second.stderr());
+}
+
+#[test]
+fn run_cmake_generators() {
+    let output_folder = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cmake");
+    std::fs::create_dir_all(&output_folder).unwrap();
+
+    let output = ConanInstall::with_recipe(Path::new("tests/conanfile.txt"))
+        .output_folder(&output_folder)
+        .detect_profile()
+        .build("missing")
+        .generator("CMakeToolchain")
+        .generator("CMakeDeps")
+        .run();
+
+    std::io::stderr().write_all(output.stderr()).unwrap();
+    assert!(output.is_success());
+    assert!(output_folder.join("conan_toolchain.cmake").is_file());
+
+    let cargo = output.parse();
+    let root = cargo.graph().root().unwrap();
+    assert_eq!(root.generators_folder(), Some(output_folder.as_path()));
+
+    let files = cargo.generated_files().unwrap();
+    assert_eq!(
+        files.cmake_toolchain(),
+        Some(output_folder.join("conan_toolchain.cmake").as_path())
+    );
+}
+
 #[test]
 fn test_shared_and_exe_link_flags() {
     let output = ConanInstall::with_recipe(Path::new("tests/conanfile.txt"))
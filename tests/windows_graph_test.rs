@@ -0,0 +1,36 @@
+//! conan2-rs dependency graph parsing tests for the Windows targets
+//!
+//! Kept in a separate test binary because the Cargo target
+//! environment variables are set for the whole test process.
+
+use conan2::CargoInstructions;
+
+fn instructions(cargo: &CargoInstructions) -> String {
+    String::from_utf8(cargo.as_bytes().to_vec()).expect("Invalid UTF-8")
+}
+
+#[test]
+fn windows_library_names() {
+    let json = br#"{"graph": {"nodes": {
+        "0": {"dependencies": {"1": {}}},
+        "1": {"name": "foo", "package_type": "static-library",
+              "cpp_info": {"root": {"libs": ["foo.lib", "libbar.a"], "system_libs": ["ws2_32"]}}}
+    }}}"#;
+
+    std::env::set_var("CARGO_CFG_TARGET_OS", "windows");
+
+    std::env::set_var("CARGO_CFG_TARGET_ENV", "msvc");
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=foo\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static:+verbatim=libbar.a\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=ws2_32\n"));
+
+    std::env::set_var("CARGO_CFG_TARGET_ENV", "gnu");
+    let cargo = CargoInstructions::from_graph_json(json).unwrap();
+    let emitted_instructions = instructions(&cargo);
+
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=foo\n"));
+    assert!(emitted_instructions.contains("cargo:rustc-link-lib=static=bar\n"));
+}